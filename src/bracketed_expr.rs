@@ -4,11 +4,14 @@
 // see the LICENSE file or <http://opensource.org/licenses/MIT>
 // also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
 
+use std::fmt;
 use quote::{format_ident, quote_spanned, ToTokens};
 use proc_macro2::{Span};
 use syn::parse::{ParseStream, Result};
 use syn::{Expr, bracketed};
 use syn::token::{Bracket};
+use crate::emit::Emitter;
+use crate::unparse::rust_source;
 
 pub struct BracketedExpr {
    pub bracket: Bracket,
@@ -18,12 +21,9 @@ pub struct BracketedExpr {
 
 impl ToTokens for BracketedExpr {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-       let ref expr = self.expr;
-       let coerce = format_ident!("to_{}", self.context, span=self.bracket.span);
-
-       (quote_spanned! {self.bracket.span=>
-          stream.push_str(&#expr.#coerce());
-       }).to_tokens(tokens);
+       let mut e = Emitter::default();
+       self.emit(&mut e);
+       e.finish().to_tokens(tokens);
     }
 }
 
@@ -31,6 +31,11 @@ impl BracketedExpr {
     pub fn span(&self) -> Span {
        self.bracket.span
     }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+       let ref expr = self.expr;
+       let coerce = format_ident!("to_{}", self.context, span=self.bracket.span);
+       e.push_display(quote_spanned!{self.bracket.span=> #expr.#coerce() }, self.bracket.span);
+    }
     pub fn parse(context: String, input: ParseStream) -> Result<Self> {
        let content;
        let content2;
@@ -40,3 +45,9 @@ impl BracketedExpr {
        Ok(BracketedExpr{ bracket:bracket1, context:context, expr:expr })
     }
 }
+
+impl fmt::Display for BracketedExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       write!(f, "[[ {} ]]", rust_source(&self.expr))
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! The items generated for a `<!Class>` struct, for use by a derive macro.
+
+use quote::{format_ident, quote_spanned, ToTokens};
+use proc_macro2::{TokenStream};
+use syn::parse::{Result, Error};
+use syn::{ItemStruct, Fields, Path, Token};
+use syn::spanned::Spanned;
+use syn::punctuated::Punctuated;
+
+/// The items that a `<!Foo>` class needs besides the struct itself.
+///
+/// `item` must have named fields, one of which is `children: Vec<FooChildren>`.
+/// The classes that may appear as children are listed on that field,
+/// `#[children(Item, Header)]`. This generates
+///
+/// * `enum FooChildren` with a `Display(Box<dyn Display>)` variant for
+///   `<?>…</?>` children and one variant per child class,
+/// * `impl Display for FooChildren`, which writes the child,
+/// * `impl Default for Foo`, which fills every field with its default.
+pub fn derive_class(item: &ItemStruct) -> Result<TokenStream> {
+   let name = &item.ident;
+   let span = name.span();
+   if !item.generics.params.is_empty() {
+      return Err(Error::new(item.generics.span(), format!("Class {} can not be generic", name)))
+   }
+   let fields = match &item.fields {
+      Fields::Named(fs) => fs,
+      _ => return Err(Error::new(span, format!("Class {} must have named fields", name)))
+   };
+   let children = match fields.named.iter().find(|f| f.ident.as_ref().map(|i| i == "children").unwrap_or(false)) {
+      Some(c) => c,
+      None => {
+         let msg = format!("Class {} needs a field children: Vec<{}Children>", name, name);
+         return Err(Error::new(span, msg))
+      }
+   };
+
+   let mut classes: Vec<Path> = Vec::new();
+   for a in children.attrs.iter().filter(|a| a.path.is_ident("children")) {
+      let ps = a.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+      classes.extend(ps);
+   }
+
+   let vis = &item.vis;
+   let child_enum = format_ident!("{}Children", name, span=span);
+   let mut variants = TokenStream::new();
+   let mut arms = TokenStream::new();
+   for c in classes.iter() {
+      let v = &c.segments.last().unwrap().ident;
+      (quote_spanned!{c.span()=>
+         #v(#c),
+      }).to_tokens(&mut variants);
+      (quote_spanned!{c.span()=>
+         #child_enum::#v(c) => ::std::fmt::Display::fmt(c, f),
+      }).to_tokens(&mut arms);
+   }
+
+   let mut defaults = TokenStream::new();
+   for f in fields.named.iter() {
+      let ident = &f.ident;
+      (quote_spanned!{f.span()=>
+         #ident: ::std::default::Default::default(),
+      }).to_tokens(&mut defaults);
+   }
+
+   Ok(quote_spanned!{span=>
+      #vis enum #child_enum {
+         Display(Box<dyn ::std::fmt::Display>),
+         #variants
+      }
+      impl ::std::fmt::Display for #child_enum {
+         fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+            match self {
+               #child_enum::Display(c) => ::std::fmt::Display::fmt(c, f),
+               #arms
+            }
+         }
+      }
+      impl ::std::default::Default for #name {
+         fn default() -> #name {
+            #name {
+               #defaults
+            }
+         }
+      }
+   })
+}
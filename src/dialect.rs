@@ -0,0 +1,101 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! The choices that distinguish one template language from another.
+//!
+//! Both `xhtml!` and `xtext!` templates share one syntax tree, `template::Template<D>`.
+//! The dialect `D` decides how that tree is written out.
+
+use crate::runtime::escape_attribute;
+use crate::unparse::attr_value_source;
+
+pub use crate::emit::Escape;
+
+/// The serialization that templates are written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+   /// Html5 writes void elements as `<br>`, boolean attributes as a bare `disabled`
+   /// and escapes with named entities such as `&lt;`.
+   Html5,
+   /// Xhtml writes void elements as `<br/>`, other empty elements as `<p></p>`,
+   /// boolean attributes as `disabled="disabled"` and escapes with named entities.
+   Xhtml,
+   /// Xml writes every empty element as `<p/>`, boolean attributes as
+   /// `disabled="disabled"` and escapes with numeric references such as `&#60;`.
+   Xml,
+}
+
+impl Output {
+   /// Whether a boolean attribute is written as its bare name.
+   pub fn minimize(self) -> bool {
+      self == Output::Html5
+   }
+}
+
+pub trait Dialect {
+   /// Whether whitespace between crumbs in the source is kept in the output.
+   const WHITESPACE: bool;
+   /// How a dynamic attribute value is escaped inside its double quotes.
+   const ATTRIBUTE: Escape;
+   /// Whether `{{ expr }}` is written with `runtime::Render`, escaping it as text,
+   /// rather than with its `Display` output unchanged. Dynamic attribute
+   /// values are then written with `runtime::AttributeValue`.
+   const RENDER: bool;
+   /// How elements are serialized.
+   const OUTPUT: Output;
+   /// The output form of a string or character literal attribute value.
+   fn quote_attribute(value: &str, is_char: bool) -> String;
+   /// Template source for an attribute value stored by `quote_attribute`.
+   fn attribute_source(stored: &str) -> String;
+   /// Whether a boolean attribute is written as its bare name in `output`.
+   fn minimize(output: Output) -> bool {
+      output.minimize()
+   }
+}
+
+/// Html markup: whitespace is kept and text is escaped.
+///
+/// Markup is written as [`Output::Xhtml`] unless another output is chosen, so a
+/// bare boolean attribute `<input disabled/>` is written `disabled="disabled"`;
+/// `Output::Html5` keeps it bare.
+pub struct XhtmlDialect;
+
+impl Dialect for XhtmlDialect {
+   const WHITESPACE: bool = true;
+   const ATTRIBUTE: Escape = Escape::Attribute;
+   const RENDER: bool = true;
+   const OUTPUT: Output = Output::Xhtml;
+   fn quote_attribute(value: &str, _is_char: bool) -> String {
+      format!("\"{}\"", escape_attribute(value))
+   }
+   fn attribute_source(stored: &str) -> String {
+      attr_value_source(stored)
+   }
+}
+
+/// Plain text: crumbs are joined without spaces, nothing is escaped and
+/// boolean attributes are written as their bare name.
+pub struct XtextDialect;
+
+impl Dialect for XtextDialect {
+   const WHITESPACE: bool = false;
+   const ATTRIBUTE: Escape = Escape::Quoted;
+   const RENDER: bool = false;
+   const OUTPUT: Output = Output::Xhtml;
+   fn quote_attribute(value: &str, is_char: bool) -> String {
+      if is_char {
+         format!("'{}'", value)
+      } else {
+         format!("{:?}", value)
+      }
+   }
+   fn attribute_source(stored: &str) -> String {
+      stored.to_string()
+   }
+   fn minimize(_output: Output) -> bool {
+      true
+   }
+}
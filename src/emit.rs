@@ -0,0 +1,188 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{quote, quote_spanned, ToTokens};
+use proc_macro2::{Span, TokenStream, Literal};
+
+use crate::runtime::{escape_attribute,escape_xml,escape_script_string};
+use crate::dialect::{Dialect,Output};
+
+/// How output must be escaped where it is written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+   /// Markup is written unchanged.
+   Markup,
+   /// The value of a double-quoted html attribute.
+   Attribute,
+   /// A double-quoted value where `"` is backslash escaped.
+   Quoted,
+   /// The body of a string literal in `<script>`.
+   Script,
+}
+
+/// Accumulates the statements that write a template into its sink.
+///
+/// `writer` is the place expression the statements write to. It must
+/// implement `runtime::Sink`. Runs of text known at expansion time are
+/// coalesced into a single write.
+pub struct Emitter {
+   pub writer: TokenStream,
+   pub escape: Escape,
+   /// The serialization chosen by the caller, rather than that of the dialect.
+   pub output: Option<Output>,
+   tokens: TokenStream,
+   pending: String,
+   pending_span: Option<Span>,
+   static_len: usize,
+}
+
+impl Emitter {
+   pub fn new(writer: TokenStream) -> Emitter {
+      Emitter {
+         writer,
+         escape: Escape::Markup,
+         output: None,
+         tokens: TokenStream::new(),
+         pending: String::new(),
+         pending_span: None,
+         static_len: 0,
+      }
+   }
+
+   /// The serialization to write elements in.
+   ///
+   /// Escaping only follows `Output::Xml` once `output` is set, which a
+   /// template or tag does for its dialect when it starts to emit.
+   pub fn output<D: Dialect>(&self) -> Output {
+      self.output.unwrap_or(D::OUTPUT)
+   }
+
+   /// An expression of type `&mut impl Sink` for dynamic output.
+   pub fn sink(&self, span: Span) -> TokenStream {
+      let w = &self.writer;
+      let w = if self.output == Some(Output::Xml) && matches!(self.escape, Escape::Markup | Escape::Attribute) {
+         quote_spanned!{span=> ::rdxl_internals::runtime::XmlSink::new(&mut (#w)) }
+      } else {
+         w.clone()
+      };
+      match self.escape {
+         Escape::Markup => quote_spanned!{span=> &mut (#w) },
+         Escape::Attribute => quote_spanned!{span=>
+            &mut ::rdxl_internals::runtime::AttributeSink::new(&mut (#w))
+         },
+         Escape::Quoted => quote_spanned!{span=>
+            &mut ::rdxl_internals::runtime::QuotedSink::new(&mut (#w))
+         },
+         Escape::Script => quote_spanned!{span=>
+            &mut ::rdxl_internals::runtime::ScriptStringSink::new(&mut (#w))
+         },
+      }
+   }
+
+   /// Write text that is known at expansion time.
+   pub fn push_str(&mut self, s: &str, span: Span) {
+      let s = match self.escape {
+         Escape::Markup => s.to_string(),
+         Escape::Attribute if self.output == Some(Output::Xml) => escape_xml(s),
+         Escape::Attribute => escape_attribute(s),
+         Escape::Quoted => s.replace("\"", "\\\""),
+         Escape::Script => escape_script_string(s),
+      };
+      self.static_len += s.len();
+      self.pending.push_str(&s);
+      if self.pending_span.is_none() {
+         self.pending_span = Some(span);
+      }
+   }
+
+   /// The number of bytes written by `push_str`, counting each nested block once.
+   pub fn static_len(&self) -> usize {
+      self.static_len
+   }
+
+   /// The complete output, if all of it is known at expansion time.
+   pub fn static_output(&self) -> Option<&str> {
+      if self.tokens.is_empty() {
+         Some(&self.pending)
+      } else {
+         None
+      }
+   }
+
+   fn flush(&mut self) {
+      if let Some(span) = self.pending_span.take() {
+         let l = Literal::string(&self.pending);
+         let w = &self.writer;
+         (quote_spanned!{span=>
+            ::rdxl_internals::runtime::Sink::push_str(&mut (#w), #l);
+         }).to_tokens(&mut self.tokens);
+         self.pending.clear();
+      }
+   }
+
+   /// Write the `Display` output of `e` as markup.
+   pub fn push_display<T: ToTokens>(&mut self, e: T, span: Span) {
+      self.flush();
+      let sink = self.sink(span);
+      (quote_spanned!{span=>
+         ::rdxl_internals::runtime::push_display(#sink, &(#e));
+      }).to_tokens(&mut self.tokens);
+   }
+
+   /// Write `e` with `runtime::Render`, escaping it as text.
+   pub fn push_render<T: ToTokens>(&mut self, e: T, span: Span) {
+      self.flush();
+      let sink = self.sink(span);
+      (quote_spanned!{span=>
+         ::rdxl_internals::runtime::Render::render(&(#e), #sink);
+      }).to_tokens(&mut self.tokens);
+   }
+
+   /// Add statements that do not write to the sink.
+   pub fn push_tokens(&mut self, ts: TokenStream) {
+      self.flush();
+      ts.to_tokens(&mut self.tokens);
+   }
+
+   /// Emit a nested block, such as the body of a loop, with the same writer and escaping.
+   pub fn block<F: FnOnce(&mut Emitter)>(&mut self, f: F) -> TokenStream {
+      let mut e = Emitter::new(self.writer.clone());
+      e.escape = self.escape;
+      e.output = self.output;
+      f(&mut e);
+      self.static_len += e.static_len;
+      e.finish()
+   }
+
+   /// Emit a nested block that writes markup to a different writer, such as
+   /// the parameter of a closure.
+   pub fn nested<F: FnOnce(&mut Emitter)>(&mut self, writer: TokenStream, f: F) -> TokenStream {
+      let mut e = Emitter::new(writer);
+      e.output = self.output;
+      f(&mut e);
+      self.static_len += e.static_len;
+      e.finish()
+   }
+
+   /// Emit output with a different escaping context.
+   pub fn escaped<F: FnOnce(&mut Emitter)>(&mut self, escape: Escape, f: F) {
+      let outer = self.escape;
+      self.escape = escape;
+      f(self);
+      self.escape = outer;
+   }
+
+   pub fn finish(mut self) -> TokenStream {
+      self.flush();
+      self.tokens
+   }
+}
+
+impl Default for Emitter {
+   fn default() -> Emitter {
+      Emitter::new(quote!{ stream })
+   }
+}
@@ -0,0 +1,47 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! The kinds of html element, which decide how a tag is parsed and written.
+//!
+//! See <https://html.spec.whatwg.org/multipage/syntax.html#elements-2>.
+
+use crate::template::TemplateName;
+
+/// How the content of an element is parsed and written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ElementKind {
+   /// An element that never has content, `<br>`.
+   Void,
+   /// Text that is not escaped and can not contain tags, `<script>`.
+   RawText,
+   /// Text and `{{ expr }}`, which is escaped, but no tags, `<textarea>`.
+   EscapableRawText,
+   /// Any other element.
+   Normal,
+}
+
+/// Elements that are [`ElementKind::Void`].
+pub const VOID: [&str; 15] = ["area", "base", "br", "col", "embed", "hr", "img", "input",
+   "keygen", "link", "meta", "param", "source", "track", "wbr"];
+
+/// Elements that are [`ElementKind::RawText`].
+pub const RAW_TEXT: [&str; 2] = ["script", "style"];
+
+/// Elements that are [`ElementKind::EscapableRawText`].
+pub const ESCAPABLE_RAW_TEXT: [&str; 2] = ["textarea", "title"];
+
+/// The kind of the element `name`. Names with a namespace prefix are always `Normal`.
+pub fn element_kind(name: &TemplateName) -> ElementKind {
+   let is = |names: &[&str]| name.prefix.is_none() && names.iter().any(|s| name.local.eq_ignore_ascii_case(s));
+   if is(&VOID) {
+      ElementKind::Void
+   } else if is(&RAW_TEXT) {
+      ElementKind::RawText
+   } else if is(&ESCAPABLE_RAW_TEXT) {
+      ElementKind::EscapableRawText
+   } else {
+      ElementKind::Normal
+   }
+}
@@ -1,21 +1,33 @@
+#[allow(clippy::clone_on_copy)]
 mod token_as_literal;
+mod rust_parse;
+mod emit;
+mod unparse;
+#[allow(clippy::redundant_field_names, clippy::toplevel_ref_arg)]
 mod bracketed_expr;
-mod xhtml_class_child;
-mod xhtml_display_expr;
-mod xhtml_class_attr;
-mod xhtml_expr;
-mod xhtml_attr;
-mod xhtml_class;
-mod xhtml_tag;
-mod xhtml_crumb;
-mod xtext_class_child;
-mod xtext_display_expr;
-mod xtext_class_attr;
-mod xtext_expr;
-mod xtext_attr;
-mod xtext_class;
-mod xtext_tag;
-mod xtext_crumb;
+mod template_class_child;
+mod template_display_expr;
+mod template_class_attr;
+mod template_expr;
+mod template_attr;
+mod template_class;
+mod template_tag;
+mod template_crumb;
+mod template_name;
+mod template_doctype;
+mod template_comment;
+mod template_raw;
+mod template_fragment;
+mod template_component;
+mod template_merged;
+mod template_validate;
+mod template_visit;
+pub mod class_derive;
 pub mod core;
+pub mod runtime;
+pub mod dialect;
+pub mod html;
+pub mod schema;
+pub mod template;
 pub mod xhtml;
 pub mod xtext;
@@ -0,0 +1,486 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Support code referenced by the generated templates.
+//!
+//! Everything the macro expansions call at runtime lives here, so the expanded
+//! code refers to it as `::rdxl_internals::runtime::*`.
+
+use std::{fmt, io};
+
+/// Escape `<`, `>`, `&`, `"` and `'` so that `text` can appear in html text content.
+pub fn escape_html(text: &str) -> String {
+   let mut stream = String::with_capacity(text.len());
+   push_escaped_html(&mut stream, text);
+   stream
+}
+
+/// Append `text` to `stream`, escaped as by [`escape_html`].
+pub fn push_escaped_html(stream: &mut String, text: &str) {
+   escape_with(text, &NAMED, |s| stream.push_str(s));
+}
+
+/// Escape the same characters as [`escape_html`] with numeric character references,
+/// `&#60;`, as `Output::Xml` writes them.
+pub fn escape_xml(text: &str) -> String {
+   let mut stream = String::with_capacity(text.len());
+   escape_with(text, &NUMERIC, |s| stream.push_str(s));
+   stream
+}
+
+/// Escape `text` for use inside a double-quoted html attribute value.
+///
+/// This is the same escaping as [`escape_html`], which already covers `"` and `'`.
+pub fn escape_attribute(text: &str) -> String {
+   escape_html(text)
+}
+
+/// Append `text` to `stream`, escaped as by [`escape_attribute`].
+pub fn push_escaped_attribute(stream: &mut String, text: &str) {
+   push_escaped_html(stream, text);
+}
+
+/// The references for `<`, `>`, `&`, `"` and `'`.
+const NAMED: [&str; 5] = ["&lt;", "&gt;", "&amp;", "&quot;", "&#39;"];
+const NUMERIC: [&str; 5] = ["&#60;", "&#62;", "&#38;", "&#34;", "&#39;"];
+
+fn escape_with<F: FnMut(&str)>(text: &str, entities: &[&str; 5], mut push: F) {
+   let mut last = 0;
+   for (i,c) in text.char_indices() {
+      let entity = match c {
+         '<' => entities[0],
+         '>' => entities[1],
+         '&' => entities[2],
+         '"' => entities[3],
+         '\'' => entities[4],
+         _ => continue
+      };
+      push(&text[last..i]);
+      push(entity);
+      last = i + 1;
+   }
+   push(&text[last..]);
+}
+
+/// A destination for rendered output.
+///
+/// The sink decides what escaping its context requires: `push_str` appends
+/// markup that is already safe, `push_text` appends arbitrary text.
+pub trait Sink {
+   fn push_str(&mut self, markup: &str);
+   fn push_text(&mut self, text: &str);
+}
+
+impl Sink for String {
+   fn push_str(&mut self, markup: &str) {
+      String::push_str(self, markup);
+   }
+   fn push_text(&mut self, text: &str) {
+      push_escaped_html(self, text);
+   }
+}
+
+impl<S: Sink + ?Sized> Sink for &mut S {
+   fn push_str(&mut self, markup: &str) {
+      (**self).push_str(markup);
+   }
+   fn push_text(&mut self, text: &str) {
+      (**self).push_text(text);
+   }
+}
+
+/// Sink for the value of a quoted attribute.
+///
+/// Both markup and text are written to the enclosing sink as text, which
+/// escapes `"` along with markup, so nothing written here can end the
+/// attribute value.
+pub struct AttributeSink<'a, S: Sink + ?Sized> {
+   inner: &'a mut S,
+}
+
+impl<'a, S: Sink + ?Sized> AttributeSink<'a, S> {
+   pub fn new(inner: &'a mut S) -> AttributeSink<'a, S> {
+      AttributeSink {
+         inner,
+      }
+   }
+}
+
+impl<'a, S: Sink + ?Sized> Sink for AttributeSink<'a, S> {
+   fn push_str(&mut self, markup: &str) {
+      self.inner.push_text(markup);
+   }
+   fn push_text(&mut self, text: &str) {
+      Sink::push_str(self, text);
+   }
+}
+
+/// Sink for `Output::Xml`, where text is escaped as by [`escape_xml`].
+pub struct XmlSink<'a, S: Sink + ?Sized> {
+   inner: &'a mut S,
+}
+
+impl<'a, S: Sink + ?Sized> XmlSink<'a, S> {
+   pub fn new(inner: &'a mut S) -> XmlSink<'a, S> {
+      XmlSink {
+         inner,
+      }
+   }
+}
+
+impl<'a, S: Sink + ?Sized> Sink for XmlSink<'a, S> {
+   fn push_str(&mut self, markup: &str) {
+      self.inner.push_str(markup);
+   }
+   fn push_text(&mut self, text: &str) {
+      let inner = &mut *self.inner;
+      escape_with(text, &NUMERIC, |s| inner.push_str(s));
+   }
+}
+
+/// Sink for a double-quoted value in the text dialect, where `"` is backslash escaped.
+pub struct QuotedSink<'a, S: Sink + ?Sized> {
+   inner: &'a mut S,
+}
+
+impl<'a, S: Sink + ?Sized> QuotedSink<'a, S> {
+   pub fn new(inner: &'a mut S) -> QuotedSink<'a, S> {
+      QuotedSink {
+         inner,
+      }
+   }
+}
+
+impl<'a, S: Sink + ?Sized> Sink for QuotedSink<'a, S> {
+   fn push_str(&mut self, markup: &str) {
+      let mut last = 0;
+      for (i,_) in markup.match_indices('"') {
+         self.inner.push_str(&markup[last..i]);
+         self.inner.push_str("\\\"");
+         last = i + 1;
+      }
+      self.inner.push_str(&markup[last..]);
+   }
+   fn push_text(&mut self, text: &str) {
+      Sink::push_str(self, text);
+   }
+}
+
+/// Escape `text` as the body of a double-quoted JavaScript string, which is also a JSON string.
+///
+/// Quotes, backslashes and control characters are escaped, and so are `<`, `>`
+/// and `&` as `\u003c`, `\u003e` and `\u0026`, so the string can not end the
+/// `<script>` element it is written into however its pieces are split.
+pub fn escape_script_string(text: &str) -> String {
+   let mut stream = String::with_capacity(text.len());
+   for c in text.chars() {
+      match c {
+         '"' => stream.push_str("\\\""),
+         '\\' => stream.push_str("\\\\"),
+         '\n' => stream.push_str("\\n"),
+         '\r' => stream.push_str("\\r"),
+         '\t' => stream.push_str("\\t"),
+         '\'' | '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => stream.push_str(&format!("\\u{:04x}", c as u32)),
+         c if c.is_control() => stream.push_str(&format!("\\u{:04x}", c as u32)),
+         c => stream.push(c),
+      }
+   }
+   stream
+}
+
+/// Sink for a value interpolated into `<script>`, inside the quotes of a string literal.
+///
+/// Both markup and text are escaped with [`escape_script_string`], so nothing
+/// written here can end the string or the element.
+pub struct ScriptStringSink<'a, S: Sink + ?Sized> {
+   inner: &'a mut S,
+}
+
+impl<'a, S: Sink + ?Sized> ScriptStringSink<'a, S> {
+   pub fn new(inner: &'a mut S) -> ScriptStringSink<'a, S> {
+      ScriptStringSink {
+         inner,
+      }
+   }
+}
+
+impl<'a, S: Sink + ?Sized> Sink for ScriptStringSink<'a, S> {
+   fn push_str(&mut self, markup: &str) {
+      self.inner.push_str(&escape_script_string(markup));
+   }
+   fn push_text(&mut self, text: &str) {
+      Sink::push_str(self, text);
+   }
+}
+
+/// Sink that writes into any `fmt::Write`, such as a `fmt::Formatter`.
+///
+/// The first error is remembered and later output is dropped.
+pub struct FmtSink<W: fmt::Write> {
+   inner: W,
+   result: fmt::Result,
+}
+
+impl<W: fmt::Write> FmtSink<W> {
+   pub fn new(inner: W) -> FmtSink<W> {
+      FmtSink {
+         inner,
+         result: Ok(()),
+      }
+   }
+   pub fn finish(self) -> fmt::Result {
+      self.result
+   }
+   pub fn into_inner(self) -> W {
+      self.inner
+   }
+}
+
+impl<W: fmt::Write> Sink for FmtSink<W> {
+   fn push_str(&mut self, markup: &str) {
+      if self.result.is_ok() {
+         self.result = self.inner.write_str(markup);
+      }
+   }
+   fn push_text(&mut self, text: &str) {
+      let inner = &mut self.inner;
+      let result = &mut self.result;
+      escape_with(text, &NAMED, |s| if result.is_ok() { *result = inner.write_str(s); });
+   }
+}
+
+/// Sink that writes into any `io::Write`, such as a buffered socket.
+///
+/// The first error is remembered and later output is dropped.
+pub struct IoSink<W: io::Write> {
+   inner: W,
+   result: io::Result<()>,
+}
+
+impl<W: io::Write> IoSink<W> {
+   pub fn new(inner: W) -> IoSink<W> {
+      IoSink {
+         inner,
+         result: Ok(()),
+      }
+   }
+   pub fn finish(self) -> io::Result<()> {
+      self.result
+   }
+   pub fn into_inner(self) -> W {
+      self.inner
+   }
+}
+
+impl<W: io::Write> Sink for IoSink<W> {
+   fn push_str(&mut self, markup: &str) {
+      if self.result.is_ok() {
+         self.result = self.inner.write_all(markup.as_bytes());
+      }
+   }
+   fn push_text(&mut self, text: &str) {
+      let inner = &mut self.inner;
+      let result = &mut self.result;
+      escape_with(text, &NAMED, |s| if result.is_ok() { *result = inner.write_all(s.as_bytes()); });
+   }
+}
+
+fn is_attribute_name(name: &str) -> bool {
+   !name.is_empty() && !name.chars().any(|c| {
+      c.is_whitespace() || c.is_control() || "\"'<>/=".contains(c)
+   })
+}
+
+/// The attributes of a spread `{{..attrs}}` in the order they are written.
+///
+/// A key given more than once keeps its first position and its last value.
+/// Keys in `written`, the attributes written on the tag itself, are dropped
+/// so that the tag overrides the spread, and so are keys that are not valid
+/// attribute names.
+pub fn spread_attributes<I, K, V>(attrs: I, written: &[&str]) -> Vec<(K, V)>
+where I: IntoIterator<Item=(K, V)>,
+      K: AsRef<str> {
+   let mut out: Vec<(K, V)> = Vec::new();
+   for (k,v) in attrs {
+      let name = k.as_ref();
+      if !is_attribute_name(name) || written.contains(&name) {
+         continue;
+      }
+      match out.iter().position(|(o,_)| o.as_ref() == name) {
+         Some(i) => { out[i].1 = v; },
+         None => { out.push((k, v)); }
+      }
+   }
+   out
+}
+
+/// The merged value of `class` or `style`, built from several contributions.
+///
+/// Items are kept in the order they are pushed. Each is trimmed of whitespace
+/// and separators, and empty items are dropped, so the value never has a
+/// doubled or dangling separator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttributeList {
+   separator: &'static str,
+   value: String,
+}
+
+impl AttributeList {
+   /// A space separated list of classes.
+   pub fn classes() -> AttributeList {
+      AttributeList {
+         separator: " ",
+         value: String::new(),
+      }
+   }
+   /// A `;` separated list of style declarations.
+   pub fn styles() -> AttributeList {
+      AttributeList {
+         separator: ";",
+         value: String::new(),
+      }
+   }
+   pub fn push<T: fmt::Display + ?Sized>(&mut self, item: &T) {
+      let item = item.to_string();
+      let separator = self.separator;
+      let item = item.trim_matches(|c: char| c.is_whitespace() || separator.contains(c));
+      if !item.is_empty() {
+         if !self.value.is_empty() {
+            self.value.push_str(separator);
+         }
+         self.value.push_str(item);
+      }
+   }
+   /// Push the declaration `name:value`, unless `value` is empty.
+   pub fn push_property<T: fmt::Display + ?Sized>(&mut self, name: &str, value: &T) {
+      let value = value.to_string();
+      if !value.trim().is_empty() {
+         self.push(&format_args!("{}:{}", name, value.trim()));
+      }
+   }
+   pub fn is_empty(&self) -> bool {
+      self.value.is_empty()
+   }
+   pub fn as_str(&self) -> &str {
+      &self.value
+   }
+}
+
+impl fmt::Display for AttributeList {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      f.write_str(&self.value)
+   }
+}
+
+/// The dynamic value of an attribute, `disabled={{ flag }}`.
+///
+/// `true` writes the attribute, as its bare name if `minimize` is set or
+/// otherwise as `name="name"`, and `false` omits it. `None` omits the
+/// attribute and `Some(v)` writes `v` as text. Values of any other `Display`
+/// type are written as text by [`DisplayAttributeValue`].
+pub trait AttributeValue {
+   /// Write ` name="value"` to `stream`, or nothing if the attribute is omitted.
+   fn push_attribute<S: Sink + ?Sized>(&self, name: &str, minimize: bool, stream: &mut S);
+}
+
+impl AttributeValue for bool {
+   fn push_attribute<S: Sink + ?Sized>(&self, name: &str, minimize: bool, stream: &mut S) {
+      if *self && minimize {
+         stream.push_str(" ");
+         stream.push_str(name);
+      } else if *self {
+         push_text_attribute(name, name, stream);
+      }
+   }
+}
+
+impl<T: fmt::Display> AttributeValue for Option<T> {
+   fn push_attribute<S: Sink + ?Sized>(&self, name: &str, _minimize: bool, stream: &mut S) {
+      if let Some(v) = self {
+         push_text_attribute(v, name, stream);
+      }
+   }
+}
+
+/// The fallback for values that do not implement [`AttributeValue`].
+///
+/// Templates call `(&(value)).push_attribute(..)` with both traits in scope.
+/// This impl is for `&T` so it is only chosen by auto-ref, after any
+/// `AttributeValue` impl for `T` itself.
+pub trait DisplayAttributeValue {
+   fn push_attribute<S: Sink + ?Sized>(&self, name: &str, minimize: bool, stream: &mut S);
+}
+
+impl<T: fmt::Display + ?Sized> DisplayAttributeValue for &T {
+   fn push_attribute<S: Sink + ?Sized>(&self, name: &str, _minimize: bool, stream: &mut S) {
+      push_text_attribute(*self, name, stream);
+   }
+}
+
+fn push_text_attribute<S: Sink + ?Sized, T: fmt::Display + ?Sized>(value: &T, name: &str, stream: &mut S) {
+   stream.push_str(" ");
+   stream.push_str(name);
+   stream.push_str("=\"");
+   value.render(&mut AttributeSink::new(stream));
+   stream.push_str("\"");
+}
+
+/// A type that is written into a template as a component, `<Card title={{t}}>…</Card>`.
+///
+/// The tag builds a value of the type from its attributes and its named slot
+/// children, `<?header>…</?>` filling a `String` field `header`, taking the
+/// other fields from `Default`. It then calls `render` with its other children
+/// as a closure that writes them into the sink it is given.
+pub trait Component {
+   fn render(&self, stream: &mut dyn Sink, children: &mut dyn FnMut(&mut dyn Sink));
+}
+
+/// Write the `Display` output of `value` to `stream` as markup, without escaping it.
+pub fn push_display<S: Sink + ?Sized, T: fmt::Display + ?Sized>(stream: &mut S, value: &T) {
+   let _ = fmt::write(&mut MarkupWriter(stream), format_args!("{}", value));
+}
+
+/// Values interpolated into a template with `{{ expr }}`.
+///
+/// Anything that implements `Display` is rendered as escaped text.
+/// Wrap trusted markup in [`PreEscaped`] to write it out unchanged.
+pub trait Render {
+   fn render<S: Sink + ?Sized>(&self, stream: &mut S);
+}
+
+impl<T: fmt::Display + ?Sized> Render for T {
+   fn render<S: Sink + ?Sized>(&self, stream: &mut S) {
+      let _ = fmt::write(&mut TextWriter(stream), format_args!("{}", self));
+   }
+}
+
+/// Markup that is trusted and must not be escaped again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PreEscaped<T: AsRef<str>>(pub T);
+
+impl<T: AsRef<str>> Render for PreEscaped<T> {
+   fn render<S: Sink + ?Sized>(&self, stream: &mut S) {
+      stream.push_str(self.0.as_ref());
+   }
+}
+
+struct TextWriter<'a, S: Sink + ?Sized>(&'a mut S);
+
+impl<'a, S: Sink + ?Sized> fmt::Write for TextWriter<'a, S> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      self.0.push_text(s);
+      Ok(())
+   }
+}
+
+struct MarkupWriter<'a, S: Sink + ?Sized>(&'a mut S);
+
+impl<'a, S: Sink + ?Sized> fmt::Write for MarkupWriter<'a, S> {
+   fn write_str(&mut self, s: &str) -> fmt::Result {
+      self.0.push_str(s);
+      Ok(())
+   }
+}
@@ -0,0 +1,49 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use syn::parse::{ParseStream, Result};
+use syn::{Token, Expr, ExprLet, Pat, PatOr};
+use syn::punctuated::Punctuated;
+
+/// Parse a pattern that may have top level alternatives, `A | B`.
+pub fn parse_pat(input: ParseStream) -> Result<Pat> {
+   let leading_vert: Option<Token![|]> = input.parse()?;
+   let pat: Pat = input.parse()?;
+   if leading_vert.is_none() && (!input.peek(Token![|]) || input.peek(Token![||])) {
+      return Ok(pat)
+   }
+   let mut cases = Punctuated::new();
+   cases.push_value(pat);
+   while input.peek(Token![|]) && !input.peek(Token![||]) {
+      let vert: Token![|] = input.parse()?;
+      cases.push_punct(vert);
+      let pat: Pat = input.parse()?;
+      cases.push_value(pat);
+   }
+   Ok(Pat::Or(PatOr { attrs: Vec::new(), leading_vert, cases }))
+}
+
+/// Parse the condition of an `if` or `while`, which may be a `let` binding.
+///
+/// The condition is followed by a `{{ }}` body, so a struct literal is not
+/// allowed to swallow the brace that starts it.
+pub fn parse_condition(input: ParseStream) -> Result<Expr> {
+   if input.peek(Token![let]) {
+      let let_token: Token![let] = input.parse()?;
+      let pat = parse_pat(input)?;
+      let eq_token: Token![=] = input.parse()?;
+      let expr = input.call(Expr::parse_without_eager_brace)?;
+      Ok(Expr::Let(ExprLet {
+         attrs: Vec::new(),
+         let_token,
+         pat,
+         eq_token,
+         expr: Box::new(expr),
+      }))
+   } else {
+      input.call(Expr::parse_without_eager_brace)
+   }
+}
@@ -0,0 +1,170 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Known `<!Class>` structs, checked while a template is parsed.
+//!
+//! Without a schema a misspelled attribute only shows up as a rustc error on
+//! the generated struct literal. A macro can describe its classes here and
+//! parse inside [`Schema::scope`], so that attributes, slots and child classes
+//! of the classes it knows are reported on the template source instead.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use proc_macro2::Span;
+use syn::parse::{Result, Error};
+
+/// How a struct field is filled from a template.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+   /// An attribute, `<!Card title="x">`.
+   Attr,
+   /// A slot child, `<?header>…</?>`.
+   Slot,
+}
+
+/// The fields and child classes of one class.
+#[derive(Clone, Debug, Default)]
+pub struct ClassSchema {
+   pub fields: BTreeMap<String, FieldKind>,
+   pub children: Vec<String>,
+}
+
+impl ClassSchema {
+   pub fn new() -> ClassSchema {
+      ClassSchema::default()
+   }
+   pub fn attr(mut self, name: &str) -> ClassSchema {
+      self.fields.insert(name.to_string(), FieldKind::Attr);
+      self
+   }
+   pub fn slot(mut self, name: &str) -> ClassSchema {
+      self.fields.insert(name.to_string(), FieldKind::Slot);
+      self
+   }
+   pub fn child(mut self, class: &str) -> ClassSchema {
+      self.children.push(class.to_string());
+      self
+   }
+}
+
+/// A registry of classes by name.
+///
+/// Classes that are not registered are not checked.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+   classes: BTreeMap<String, ClassSchema>,
+}
+
+thread_local! {
+   #[allow(clippy::missing_const_for_thread_local)]
+   static CURRENT: RefCell<Option<Rc<Schema>>> = RefCell::new(None);
+}
+
+/// Puts back the schema that was current before [`Schema::scope`], even if `f` panics.
+struct Restore(Option<Rc<Schema>>);
+
+impl Drop for Restore {
+   fn drop(&mut self) {
+      let outer = self.0.take();
+      CURRENT.with(|c| c.replace(outer));
+   }
+}
+
+impl Schema {
+   pub fn new() -> Schema {
+      Schema::default()
+   }
+   pub fn class(mut self, name: &str, class: ClassSchema) -> Schema {
+      self.classes.insert(name.to_string(), class);
+      self
+   }
+   pub fn get(&self, name: &str) -> Option<&ClassSchema> {
+      self.classes.get(name)
+   }
+
+   /// Run `f`, such as `syn::parse2::<Xhtml>(ts)`, with this schema checking classes.
+   pub fn scope<R, F: FnOnce() -> R>(&self, f: F) -> R {
+      let _restore = Restore(CURRENT.with(|c| c.replace(Some(Rc::new(self.clone())))));
+      f()
+   }
+}
+
+fn with_class<F: FnOnce(&ClassSchema) -> Result<()>>(class: &str, f: F) -> Result<()> {
+   let schema = CURRENT.with(|c| c.borrow().clone());
+   match schema.as_ref().and_then(|s| s.get(class)) {
+      Some(c) => f(c),
+      None => Ok(())
+   }
+}
+
+fn distance(a: &str, b: &str) -> usize {
+   let b: Vec<char> = b.chars().collect();
+   let mut row: Vec<usize> = (0..=b.len()).collect();
+   for (i,ca) in a.chars().enumerate() {
+      let mut diag = row[0];
+      row[0] = i + 1;
+      for (j,cb) in b.iter().enumerate() {
+         let next = (row[j+1] + 1).min(row[j] + 1).min(diag + if ca == *cb { 0 } else { 1 });
+         diag = row[j+1];
+         row[j+1] = next;
+      }
+   }
+   row[b.len()]
+}
+
+/// The candidate closest to `name`, if it is close enough to be a likely typo.
+fn suggest<'a, I: Iterator<Item=&'a String>>(name: &str, candidates: I) -> String {
+   let best = candidates
+      .map(|c| (distance(name, c), c))
+      .filter(|(d,c)| *d <= (c.len() / 3).max(1))
+      .min();
+   match best {
+      Some((_,c)) => format!(", did you mean {}?", c),
+      None => String::new()
+   }
+}
+
+/// Check the attribute `key` of `<!class>`.
+pub(crate) fn check_attr(class: &str, key: &str, span: Span) -> Result<()> {
+   with_class(class, |c| match c.fields.get(key) {
+      Some(FieldKind::Attr) => Ok(()),
+      Some(FieldKind::Slot) => {
+         let msg = format!("{} of <!{}> is a slot, write it as <?{}>…</?>", key, class, key);
+         Err(Error::new(span, msg))
+      },
+      None => {
+         let names = c.fields.iter().filter(|(_,k)| **k == FieldKind::Attr).map(|(n,_)| n);
+         let msg = format!("<!{}> has no attribute {}{}", class, key, suggest(key, names));
+         Err(Error::new(span, msg))
+      }
+   })
+}
+
+/// Check the slot child `<?slot>` of `<!class>`.
+pub(crate) fn check_slot(class: &str, slot: &str, span: Span) -> Result<()> {
+   with_class(class, |c| match c.fields.get(slot) {
+      Some(FieldKind::Slot) => Ok(()),
+      Some(FieldKind::Attr) => {
+         let msg = format!("{} of <!{}> is an attribute, not a slot", slot, class);
+         Err(Error::new(span, msg))
+      },
+      None => {
+         let names = c.fields.iter().filter(|(_,k)| **k == FieldKind::Slot).map(|(n,_)| n);
+         let msg = format!("<!{}> has no slot {}{}", class, slot, suggest(slot, names));
+         Err(Error::new(span, msg))
+      }
+   })
+}
+
+/// Check that `<!child>` may appear inside `<!class>`.
+pub(crate) fn check_child(class: &str, child: &str, span: Span) -> Result<()> {
+   with_class(class, |c| if c.children.iter().any(|n| n == child) {
+      Ok(())
+   } else {
+      let msg = format!("<!{}> can not contain <!{}>{}", class, child, suggest(child, c.children.iter()));
+      Err(Error::new(span, msg))
+   })
+}
@@ -0,0 +1,149 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use std::marker::PhantomData;
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Span, Literal};
+use syn::parse::{Parse, ParseStream, Result};
+
+pub use crate::bracketed_expr::BracketedExpr;
+pub use crate::template_display_expr::{TemplateDisplayExpr,TemplateDisplay};
+pub use crate::template_expr::{TemplateExpr,TemplateExprInner};
+pub use crate::template_class_attr::TemplateClassAttr;
+pub use crate::template_attr::TemplateAttr;
+pub use crate::template_class_child::TemplateClassChild;
+pub use crate::template_class::TemplateClass;
+pub use crate::template_tag::{TemplateTag,TemplateAttrKey,TemplateClose};
+pub use crate::template_crumb::TemplateCrumb;
+pub use crate::template_name::TemplateName;
+pub use crate::template_doctype::TemplateDoctype;
+pub use crate::template_comment::TemplateComment;
+pub use crate::template_fragment::TemplateFragment;
+use crate::emit::Emitter;
+use crate::template_validate::validate;
+use crate::dialect::{Dialect,Output};
+
+pub mod visit {
+    pub use crate::template_visit::*;
+}
+
+pub struct Template<D> {
+    pub crumbs: Vec<TemplateCrumb<D>>,
+    pub dialect: PhantomData<fn() -> D>,
+}
+
+impl<D: Dialect> Template<D> {
+    pub fn new(crumbs: Vec<TemplateCrumb<D>>) -> Template<D> {
+       Template {
+          crumbs,
+          dialect: PhantomData,
+       }
+    }
+    pub fn span(&self) -> Span {
+       if !self.crumbs.is_empty() {
+          let mut span = self.crumbs[0].span();
+          for c in self.crumbs[1..].iter() {
+             span = span.join(c.span()).unwrap_or(c.span());
+          }
+          span
+       } else {
+          Span::call_site()
+       }
+    }
+}
+
+impl<D: Dialect> Template<D> {
+    /// Emit statements that write this template into `writer`.
+    ///
+    /// `writer` must be a mutable place expression whose type implements
+    /// `rdxl_internals::runtime::Sink`, such as a `String` named `stream`. To write
+    /// into a `fmt::Formatter` `f`, bind `let mut sink = FmtSink::new(f);` and pass `sink`.
+    pub fn to_tokens_into<W: ToTokens>(&self, writer: &W, tokens: &mut proc_macro2::TokenStream) {
+        self.to_tokens_with(writer, D::OUTPUT, tokens);
+    }
+    /// Emit statements that write this template into `writer`, serialized as `output`
+    /// rather than as the dialect's default.
+    pub fn to_tokens_with<W: ToTokens>(&self, writer: &W, output: Output, tokens: &mut proc_macro2::TokenStream) {
+        let mut e = Emitter::new(writer.to_token_stream());
+        e.output = Some(output);
+        self.emit(&mut e);
+        e.finish().to_tokens(tokens);
+    }
+    /// The number of bytes of output known at expansion time, for `String::with_capacity`.
+    pub fn capacity_hint(&self) -> usize {
+        let mut e = Emitter::default();
+        self.emit(&mut e);
+        e.static_len()
+    }
+    /// The whole output of this template, if it is known at expansion time.
+    pub fn as_static_str(&self) -> Option<String> {
+        self.as_static_str_with(D::OUTPUT)
+    }
+    /// The whole output of this template serialized as `output`, if it is known at expansion time.
+    pub fn as_static_str_with(&self, output: Output) -> Option<String> {
+        let mut e = Emitter::default();
+        e.output = Some(output);
+        self.emit(&mut e);
+        e.static_output().map(|s| s.to_string())
+    }
+    /// A `&'static str` literal of the output, if it is known at expansion time.
+    pub fn to_static_tokens(&self) -> Option<proc_macro2::TokenStream> {
+        self.as_static_str().map(|s| {
+            let l = Literal::string(&s);
+            quote_spanned!{self.span()=> #l }
+        })
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+        e.output.get_or_insert(D::OUTPUT);
+        let mut prev: Option<Span> = None;
+        for c in self.crumbs.iter() {
+            let span = c.span();
+            if let Some(sp) = prev {
+            if D::WHITESPACE && c.does_emit() && span.start() > sp.end() {
+               e.push_str(" ", span);
+            }}
+
+            prev = Some(span);
+            c.emit(e);
+        }
+    }
+}
+
+impl<D: Dialect> ToTokens for Template<D> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let mut e = Emitter::default();
+        self.emit(&mut e);
+        e.finish().to_tokens(tokens);
+    }
+}
+
+impl<D: Dialect> fmt::Display for Template<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        TemplateCrumb::fmt_crumbs(&self.crumbs, f)
+    }
+}
+
+impl<D: Dialect> Template<D> {
+    /// Parse the content of a tag, up to its closing tag.
+    pub fn parse_inner(input: ParseStream) -> Result<Self> {
+        let crumbs: Vec<TemplateCrumb<D>> = input.call(TemplateCrumb::parse_outer)?;
+        Ok(Template::new(crumbs))
+    }
+    /// Report every structural error in the template, combined into one error.
+    pub fn validate(&self) -> Result<()> {
+        validate(self)
+    }
+}
+
+impl<D: Dialect> Parse for Template<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let crumbs: Vec<TemplateCrumb<D>> = input.call(TemplateCrumb::parse_body)?;
+        let t = Template::new(crumbs);
+        t.validate()?;
+        Ok(t)
+    }
+}
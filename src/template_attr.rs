@@ -0,0 +1,55 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use syn::parse::{ParseStream, Result};
+use syn::{LitChar, LitBool, LitStr, LitInt};
+use syn::token::{Bracket,Brace};
+use crate::template::{TemplateExpr,TemplateFragment,BracketedExpr};
+use crate::dialect::Dialect;
+
+pub enum TemplateAttr<D> {
+   S(String),
+   F(BracketedExpr),
+   E(TemplateExpr<D>),
+   G(TemplateFragment<D>),
+}
+impl<D: Dialect> TemplateAttr<D> {
+   pub fn parse(input: ParseStream, key: String) -> Result<Self> {
+      if input.peek(Bracket) {
+         let f: BracketedExpr = BracketedExpr::parse(key.clone(),input)?;
+         Ok(TemplateAttr::F(f))
+      } else if TemplateFragment::<D>::peek(input) {
+         let g: TemplateFragment<D> = input.parse()?;
+         Ok(TemplateAttr::G(g))
+      } else if input.peek(Brace) {
+         let e: TemplateExpr<D> = input.parse()?;
+         Ok(TemplateAttr::E(e))
+      } else if input.peek(LitBool) {
+         let b: LitBool = input.parse()?;
+         Ok(TemplateAttr::S(format!("{}", b.value)))
+      } else if input.peek(LitInt) {
+         let b: LitInt = input.parse()?;
+         Ok(TemplateAttr::S(b.base10_digits().to_string()))
+      } else if input.peek(LitChar) {
+         let b: LitChar = input.parse()?;
+         Ok(TemplateAttr::S(D::quote_attribute(&b.value().to_string(), true)))
+      } else {
+         let val: LitStr = input.parse()?;
+         Ok(TemplateAttr::S(D::quote_attribute(&val.value(), false)))
+      }
+   }
+}
+impl<D: Dialect> fmt::Display for TemplateAttr<D> {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         TemplateAttr::S(s) => write!(f, "{}", D::attribute_source(s)),
+         TemplateAttr::F(b) => write!(f, "{}", b),
+         TemplateAttr::E(e) => write!(f, "{}", e),
+         TemplateAttr::G(g) => write!(f, "{}", g),
+      }
+   }
+}
@@ -0,0 +1,183 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use quote::{format_ident,quote_spanned, ToTokens};
+use proc_macro2::{Span, TokenStream};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Ident, Token};
+
+pub use crate::template::{TemplateClassChild,TemplateClassAttr};
+use crate::unparse::write_spaced;
+use crate::dialect::{Dialect,Output};
+use crate::schema::{check_attr, check_slot, check_child};
+
+pub struct TemplateClass<D> {
+   pub open: Token![<],
+   pub name: String,
+   pub attrs: Vec<(String,TemplateClassAttr<D>)>,
+   pub children: Vec<TemplateClassChild<D>>,
+   pub close: Token![>]
+}
+
+impl<D: Dialect> TemplateClass<D> {
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+}
+
+impl<D: Dialect> TemplateClass<D> {
+    /// The struct expression for this class, with its markup children serialized
+    /// as `output` or as the dialect's default.
+    pub(crate) fn tokens_with(&self, output: Option<Output>) -> TokenStream {
+       let mut ds = TokenStream::new();
+       let span = self.span();
+       let name = format_ident!("{}", self.name, span=span);
+
+       for (k,v) in self.attrs.iter() {
+          let k = format_ident!("{}", k, span=span);
+          let v = match v {
+             TemplateClassAttr::Cl(cl) => cl.tokens_with(output),
+             v => v.to_token_stream(),
+          };
+          (quote_spanned!{span=>
+            #k: #v,
+          }).to_tokens(&mut ds);
+       }
+
+       let mut cs = TokenStream::new();
+       for c in self.children.iter() {
+          match c {
+             TemplateClassChild::C(c) => {
+                let span = c.span();
+                let child_enum = format_ident!("{}Children", self.name, span=span);
+                let child_tag = format_ident!("{}", c.name, span=span);
+                let c = c.tokens_with(output);
+                (quote_spanned!{span=>
+                   #child_enum::#child_tag(#c),
+                }).to_tokens(&mut cs);
+             },
+             TemplateClassChild::D(d) if d.slot.is_some() => {
+                let slot = &d.slot;
+                let d = d.tokens_with(output);
+                (quote_spanned!{span=>
+                  #slot: #d,
+                }).to_tokens(&mut ds);
+             },
+             TemplateClassChild::D(d) => {
+                let span = d.span();
+                let child_enum = format_ident!("{}Children", self.name, span=span);
+                let d = d.tokens_with(output);
+                (quote_spanned!{span=>
+                   #child_enum::Display(Box::new(#d)),
+                }).to_tokens(&mut cs);
+             }
+          }
+       }
+
+       (quote_spanned!{span=>
+          children: vec![#cs],
+          ..std::default::Default::default()
+       }).to_tokens(&mut ds);
+
+       quote_spanned!{span=>
+          #name {
+             #ds
+          }
+       }
+    }
+}
+
+impl<D: Dialect> ToTokens for TemplateClass<D> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+       self.tokens_with(None).to_tokens(tokens);
+    }
+}
+
+impl<D: Dialect> fmt::Display for TemplateClass<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       write!(f, "<!{}", self.name)?;
+       for (k,v) in self.attrs.iter() {
+          write!(f, " {}={}", k, v)?;
+       }
+       if self.children.is_empty() {
+          write!(f, "/>")
+       } else {
+          write!(f, ">")?;
+          let pieces: Vec<(Span,String)> = self.children.iter().map(|c| (c.span(), c.to_string())).collect();
+          write_spaced(f, &pieces)?;
+          write!(f, "</{}>", self.name)
+       }
+    }
+}
+
+impl<D: Dialect> Parse for TemplateClass<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _ex: Token![!] = input.parse()?;
+       let name: Ident = input.parse()?;
+
+       let mut attrs = Vec::new();
+       while input.peek(Ident) {
+          let attr_name: Ident = input.parse()?;
+          check_attr(&name.to_string(), &attr_name.to_string(), attr_name.span())?;
+          let _eq: Token![=] = input.parse()?;
+          let attr_val = TemplateClassAttr::parse(input, attr_name.to_string())?;
+          attrs.push((attr_name.to_string(), attr_val));
+       }
+
+       if input.peek(Token![/]) {
+          let _slash: Token![/] = input.parse()?;
+          let close: Token![>] = input.parse()?;
+          Ok(TemplateClass {
+             open,
+             name: name.to_string(),
+             attrs,
+             children: Vec::new(),
+             close
+          })
+       } else {
+          let _gt: Token![>] = input.parse()?;
+          
+          let mut children: Vec<TemplateClassChild<D>> = Vec::new();
+          while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+             let c: TemplateClassChild<D> = input.parse()?;
+             if let TemplateClassChild::C(child) = &c {
+                check_child(&name.to_string(), &child.name, child.span())?;
+             }
+             if let Some(slot) = c.slot() {
+                let s = slot.to_string();
+                check_slot(&name.to_string(), &s, slot.span())?;
+                if attrs.iter().any(|(k,_)| *k == s) || children.iter().any(|d| d.slot().map(|d| d.to_string()) == Some(s.clone())) {
+                   let msg = format!("Slot {} of <!{}> is already given", s, name);
+                   return Err(Error::new(slot.span(), msg))
+                }
+             }
+             children.push(c);
+          }
+
+          let _lt: Token![<] = input.parse()?;
+          let _slash: Token![/] = input.parse()?;
+
+          let close_tag: Ident = input.parse()?;
+          if close_tag != name {
+              let msg = format!("Expected </{}> found </{}>", name, close_tag);
+              let r = Error::new(close_tag.span(), msg);
+              return Err(r)
+           }
+
+          let close: Token![>] = input.parse()?;
+
+          Ok(TemplateClass {
+             open,
+             name: name.to_string(),
+             attrs,
+             children,
+             close
+          })
+       }
+    }
+}
@@ -0,0 +1,52 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Ident, Token};
+use crate::template::{TemplateClass,TemplateDisplayExpr};
+use crate::dialect::Dialect;
+
+pub enum TemplateClassChild<D> {
+   C(TemplateClass<D>),
+   D(TemplateDisplayExpr<D>)
+}
+impl<D: Dialect> TemplateClassChild<D> {
+    /// The struct field filled by this child, for a slot `<?header>…</?>`.
+    pub fn slot(&self) -> Option<&Ident> {
+       match self {
+          TemplateClassChild::D(d) => d.slot.as_ref(),
+          _ => None,
+       }
+    }
+    pub fn span(&self) -> Span {
+       match self {
+          TemplateClassChild::C(c) => c.span(),
+          TemplateClassChild::D(d) => d.span(),
+       }
+    }
+}
+impl<D: Dialect> Parse for TemplateClassChild<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+       if input.peek(Token![<]) && input.peek2(Token![?]) {
+          let d: TemplateDisplayExpr<D> = input.parse()?;
+          Ok(TemplateClassChild::D(d))
+       } else {
+          let c: TemplateClass<D> = input.parse()?;
+          Ok(TemplateClassChild::C(c))
+       }
+    }
+}
+
+impl<D: Dialect> fmt::Display for TemplateClassChild<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       match self {
+          TemplateClassChild::C(c) => write!(f, "{}", c),
+          TemplateClassChild::D(d) => write!(f, "{}", d),
+       }
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use proc_macro2::{Span};
+use syn::parse::{ParseStream, Result, Error};
+use syn::{Token};
+use crate::core::TokenAsLiteral;
+use crate::emit::Emitter;
+use crate::unparse::{text_source,write_spaced};
+
+/// The text of a comment, `<!-- text -->` in the output or `<!--- text --->`
+/// in the template only.
+pub struct TemplateComment {
+   pub text: Vec<(String,Span)>,
+   pub span: Span,
+}
+
+impl TemplateComment {
+    /// Whether a comment that is written to the output starts here.
+    pub fn peek(input: ParseStream) -> bool {
+       input.peek(Token![<]) && input.peek2(Token![!]) && input.peek3(Token![-])
+    }
+    /// Whether a comment that is stripped from the output starts here.
+    pub fn peek_stripped(input: ParseStream) -> bool {
+       let f = input.fork();
+       f.parse::<Token![<]>().is_ok() &&
+       f.parse::<Token![!]>().is_ok() &&
+       f.parse::<Token![-]>().is_ok() &&
+       f.parse::<Token![-]>().is_ok() &&
+       f.peek(Token![-])
+    }
+    /// The text of the comment as written to the output.
+    pub fn value(&self) -> String {
+       self.text.iter().map(|(s,_)| s.as_str()).collect::<Vec<&str>>().join(" ")
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+       e.push_str(&format!("<!-- {} -->", self.value()), self.span);
+    }
+    /// Parse `<!-- text -->`, or `<!--- text --->` if `stripped`.
+    pub fn parse(input: ParseStream, stripped: bool) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _ex: Token![!] = input.parse()?;
+       let _d1: Token![-] = input.parse()?;
+       let _d2: Token![-] = input.parse()?;
+       if stripped {
+          let _d3: Token![-] = input.parse()?;
+       }
+       let mut text = Vec::new();
+       loop {
+          let end = if stripped {
+             input.peek(Token![-]) && input.peek2(Token![-]) && input.peek3(Token![->])
+          } else {
+             input.peek(Token![-]) && input.peek2(Token![->])
+          };
+          if end || input.is_empty() { break; }
+          let t: TokenAsLiteral = input.parse()?;
+          text.push((t.token_literal, t.span));
+       }
+       if stripped {
+          let _d1: Token![-] = input.parse()?;
+       }
+       let _d2: Token![-] = input.parse()?;
+       let close: Token![->] = input.parse()?;
+       let c = TemplateComment {
+          text,
+          span: open.span.join(close.spans[1]).unwrap_or(open.span),
+       };
+       let v = c.value();
+       if v.contains("--") || v.ends_with('-') {
+          return Err(Error::new(c.span, "A comment can not contain `--` or end with `-`"));
+       }
+       Ok(c)
+    }
+    pub(crate) fn fmt_text(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       let pieces: Vec<(Span,String)> = self.text.iter().map(|(s,sp)| (*sp, text_source(s))).collect();
+       write_spaced(f, &pieces)
+    }
+}
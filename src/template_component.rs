@@ -0,0 +1,158 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{format_ident, quote_spanned, ToTokens};
+use proc_macro2::{Span, TokenStream};
+use syn::parse::{ParseStream, Result, Error};
+use syn::{Lit, Token};
+use crate::template::{Template,TemplateTag,TemplateAttrKey,TemplateAttr,TemplateExprInner,TemplateName,TemplateDisplayExpr};
+use crate::emit::Emitter;
+use crate::dialect::Dialect;
+
+/// Whether a tag names a component rather than an element.
+///
+/// Component names start with an uppercase letter and are not all uppercase,
+/// so `<Card>` is a component while `<HTML>` and `<my-card>` are elements.
+pub fn is_component(name: &TemplateName) -> bool {
+   name.prefix.is_none() &&
+   name.local.starts_with(|c: char| c.is_ascii_uppercase()) &&
+   name.local.chars().any(|c| c.is_ascii_lowercase()) &&
+   name.local.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The struct field that the attribute `k` of a component fills.
+pub(crate) fn field_name(k: &TemplateName) -> String {
+   k.local.replace("-", "_")
+}
+
+/// Parse the children of a component, taking out its named slots `<?header>…</?>`.
+pub fn parse_children<D: Dialect>(input: ParseStream, name: &TemplateName, attrs: &[(TemplateAttrKey,Option<TemplateAttr<D>>)]) -> Result<(Template<D>, Vec<TemplateDisplayExpr<D>>)> {
+   let mut crumbs = Vec::new();
+   let mut slots: Vec<TemplateDisplayExpr<D>> = Vec::new();
+   while !(input.is_empty() || input.peek(Token![<]) && input.peek2(Token![/])) {
+      if input.peek(Token![<]) && input.peek2(Token![?]) {
+         let d: TemplateDisplayExpr<D> = input.parse()?;
+         let slot = match &d.slot {
+            Some(s) => s,
+            None => {
+               let msg = format!("Children of component <{}> are passed to render, only named slots <?name> are allowed", name);
+               return Err(Error::new(d.open.span, msg))
+            }
+         };
+         let given = attrs.iter().any(|(k,_)| match k {
+            TemplateAttrKey::S(k) => *slot == field_name(k),
+            _ => false
+         });
+         if given || slots.iter().any(|s| s.slot.as_ref() == Some(slot)) {
+            let msg = format!("Slot {} of <{}> is already given", slot, name);
+            return Err(Error::new(slot.span(), msg))
+         }
+         slots.push(d);
+      } else {
+         crumbs.push(input.parse()?);
+      }
+   }
+   Ok((Template::new(crumbs), slots))
+}
+
+/// Check that the attributes of a component can be written as its fields, each once.
+pub fn check_component<D: Dialect>(name: &TemplateName, attrs: &[(TemplateAttrKey,Option<TemplateAttr<D>>)]) -> Result<()> {
+   let mut fields: Vec<String> = Vec::new();
+   for (k,v) in attrs.iter() {
+      match k {
+         TemplateAttrKey::G(_,k) => {
+            let msg = format!("Component <{}> can not have a conditional attribute {}", name, k);
+            return Err(Error::new(k.span, msg))
+         },
+         TemplateAttrKey::P(_,span) => {
+            let msg = format!("Component <{}> can not have a spread of attributes", name);
+            return Err(Error::new(*span, msg))
+         },
+         TemplateAttrKey::S(k) => {
+            if k.prefix.is_some() {
+               let msg = format!("Component <{}> can not have a namespaced attribute {}", name, k);
+               return Err(Error::new(k.span, msg))
+            }
+            if let Some(TemplateAttr::E(x)) = v {
+               if let TemplateExprInner::E(_) = x.expr {} else {
+                  let msg = format!("Attribute {} of component <{}> must be a {{{{ expr }}}}", k, name);
+                  return Err(Error::new(x.brace_token1.span, msg))
+               }
+            }
+            let f = field_name(k);
+            if fields.contains(&f) {
+               let msg = format!("Field {} of <{}> is already given", f, name);
+               return Err(Error::new(k.span, msg))
+            }
+            fields.push(f);
+         }
+      }
+   }
+   Ok(())
+}
+
+fn prop_value<D: Dialect>(e: &mut Emitter, v: &Option<TemplateAttr<D>>, span: Span) -> TokenStream {
+   match v {
+      None => quote_spanned!{span=> true },
+      Some(TemplateAttr::S(s)) => {
+         match syn::parse_str::<Lit>(&D::attribute_source(s)) {
+            Ok(Lit::Str(l)) => quote_spanned!{span=> ::std::convert::Into::into(#l) },
+            Ok(l) => l.to_token_stream(),
+            Err(_) => quote_spanned!{span=> ::std::convert::Into::into(#s) },
+         }
+      },
+      Some(TemplateAttr::F(b)) => {
+         let expr = &b.expr;
+         let coerce = format_ident!("to_{}", b.context, span=b.bracket.span);
+         quote_spanned!{span=> (#expr).#coerce() }
+      },
+      Some(TemplateAttr::E(x)) => {
+         match &x.expr {
+            TemplateExprInner::E(expr) => quote_spanned!{span=> (#expr) },
+            _ => quote_spanned!{span=> compile_error!("expected {{ expr }}") },
+         }
+      },
+      Some(TemplateAttr::G(g)) => {
+         let body = e.nested(quote_spanned!{span=> stream }, |e| g.emit(e));
+         quote_spanned!{span=>
+            {
+               let mut stream = String::new();
+               #body
+               stream
+            }
+         }
+      },
+   }
+}
+
+/// Emit a call of `runtime::Component::render` for a component tag.
+pub(crate) fn emit_component<D: Dialect>(t: &TemplateTag<D>, e: &mut Emitter) {
+   let span = t.outer_span;
+   let name = format_ident!("{}", t.tag.local, span=span);
+   let mut fields = TokenStream::new();
+   for (k,v) in t.attrs.iter() {
+      if let TemplateAttrKey::S(k) = k {
+         let f = format_ident!("{}", field_name(k), span=span);
+         let v = prop_value(e, v, span);
+         (quote_spanned!{span=> #f: #v, }).to_tokens(&mut fields);
+      }
+   }
+   for d in t.slots.iter() {
+      let slot = &d.slot;
+      let d = d.tokens_with(e.output);
+      (quote_spanned!{span=> #slot: #d, }).to_tokens(&mut fields);
+   }
+   let body = e.nested(quote_spanned!{span=> *stream }, |e| t.inner.emit(e));
+   // A component without children must not leave the closure argument unused.
+   let stream = if body.is_empty() { quote_spanned!{span=> _ } } else { quote_spanned!{span=> stream } };
+   let sink = e.sink(span);
+   e.push_tokens(quote_spanned!{span=>
+      ::rdxl_internals::runtime::Component::render(
+         &#name { #fields ..::std::default::Default::default() },
+         #sink,
+         &mut |#stream: &mut dyn ::rdxl_internals::runtime::Sink| { #body }
+      );
+   });
+}
@@ -0,0 +1,190 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use quote::{ToTokens};
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Token};
+use syn::token::{Bracket,Brace};
+
+use crate::core::{TokenAsLiteral};
+use crate::template::{TemplateTag,TemplateExpr,BracketedExpr,TemplateClass,TemplateDoctype,TemplateComment,TemplateFragment,TemplateName};
+use crate::emit::Emitter;
+use crate::unparse::{text_source,write_spaced};
+use crate::dialect::Dialect;
+
+pub enum TemplateCrumb<D> {
+   S(String, Span),
+   T(TemplateTag<D>),
+   E(TemplateExpr<D>),
+   F(BracketedExpr),
+   C(TemplateClass<D>),
+   D(TemplateDoctype),
+   M(TemplateComment),
+   N(TemplateComment),
+   G(TemplateFragment<D>),
+}
+
+impl<D: Dialect> TemplateCrumb<D> {
+    pub fn does_emit(&self) -> bool {
+       match self {
+          TemplateCrumb::S(_,_) => { true },
+          TemplateCrumb::T(_) => { true },
+          TemplateCrumb::E(e) => { e.does_emit() },
+          TemplateCrumb::F(_) => { true },
+          TemplateCrumb::C(_) => { true },
+          TemplateCrumb::D(_) => { true },
+          TemplateCrumb::M(_) => { true },
+          TemplateCrumb::N(_) => { false },
+          TemplateCrumb::G(g) => { g.inner.crumbs.iter().any(|c| c.does_emit()) },
+       }
+    }
+    pub fn span(&self) -> Span {
+        match self {
+            TemplateCrumb::S(_,sp) => { *sp }
+            TemplateCrumb::T(t) => { t.outer_span }
+            TemplateCrumb::E(e) => { e.brace_token1.span }
+            TemplateCrumb::F(f) => { f.span() }
+            TemplateCrumb::C(c) => { c.open.span.join(c.close.span).unwrap_or(c.open.span) }
+            TemplateCrumb::D(d) => { d.span }
+            TemplateCrumb::M(m) => { m.span }
+            TemplateCrumb::N(n) => { n.span }
+            TemplateCrumb::G(g) => { g.span() }
+        }
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+        match self {
+           TemplateCrumb::S(s,span) => {
+              e.push_str(s, *span);
+           },
+           TemplateCrumb::T(t) => {
+              t.emit(e);
+           }
+           TemplateCrumb::E(x) => {
+              x.emit(e);
+           }
+           TemplateCrumb::F(f) => {
+              f.emit(e);
+           }
+           TemplateCrumb::C(c) => {
+              e.push_display(c.tokens_with(e.output), c.span());
+           }
+           TemplateCrumb::D(d) => {
+              d.emit(e);
+           }
+           TemplateCrumb::M(m) => {
+              m.emit(e);
+           }
+           TemplateCrumb::N(_) => {}
+           TemplateCrumb::G(g) => {
+              g.emit(e);
+           }
+        }
+    }
+    pub(crate) fn fmt_crumbs(cs: &[TemplateCrumb<D>], f: &mut fmt::Formatter) -> fmt::Result {
+        let pieces: Vec<(Span,String)> = cs.iter().map(|c| (c.span(), c.to_string())).collect();
+        write_spaced(f, &pieces)
+    }
+    pub fn parse_outer(input: ParseStream) -> Result<Vec<Self>> {
+        let mut cs = vec!();
+        while !(input.is_empty() || input.peek(Token![<]) && input.peek2(Token![/])) {
+           let c: TemplateCrumb<D> = input.parse()?;
+           cs.push(c);
+        }
+        Ok(cs)
+    }
+    /// Parse crumbs up to the end of `input`, reporting each `</x>` left over as stray.
+    pub fn parse_body(input: ParseStream) -> Result<Vec<Self>> {
+        let mut cs = input.call(TemplateCrumb::parse_outer)?;
+        let mut errors: Option<Error> = None;
+        while !input.is_empty() {
+           let lt: Token![<] = input.parse()?;
+           let _slash: Token![/] = input.parse()?;
+           let e = if input.peek(Token![>]) {
+              Error::new(lt.span, "Stray closing tag </>")
+           } else {
+              let t: TemplateName = input.parse()?;
+              Error::new(t.span, format!("Stray closing tag </{}>", t))
+           };
+           let _gt: Token![>] = input.parse()?;
+           match &mut errors {
+              Some(es) => es.combine(e),
+              None => { errors = Some(e); }
+           }
+           cs.extend(input.call(TemplateCrumb::parse_outer)?);
+        }
+        match errors {
+           Some(e) => Err(e),
+           None => Ok(cs)
+        }
+    }
+}
+
+impl<D: Dialect> Parse for TemplateCrumb<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if TemplateComment::peek_stripped(input) {
+           let n = TemplateComment::parse(input, true)?;
+           Ok(TemplateCrumb::N(n))
+        } else if TemplateComment::peek(input) {
+           let m = TemplateComment::parse(input, false)?;
+           Ok(TemplateCrumb::M(m))
+        } else if TemplateFragment::<D>::peek(input) {
+           let g: TemplateFragment<D> = input.parse()?;
+           Ok(TemplateCrumb::G(g))
+        } else if TemplateDoctype::peek(input) {
+           let d: TemplateDoctype = input.parse()?;
+           Ok(TemplateCrumb::D(d))
+        } else if input.peek(Token![<]) && input.peek2(Token![!]) {
+           let c: TemplateClass<D> = input.parse()?;
+           Ok(TemplateCrumb::C(c))
+        } else if input.peek(Token![<]) {
+           let t: TemplateTag<D> = input.call(TemplateTag::parse_unchecked)?;
+           Ok(TemplateCrumb::T(t))
+        } else if input.peek(Bracket) {
+           let f: BracketedExpr = BracketedExpr::parse("markup".to_string(),input)?;
+           Ok(TemplateCrumb::F(f))
+        } else if input.peek(Brace) {
+           let e: TemplateExpr<D> = input.parse()?;
+           Ok(TemplateCrumb::E(e))
+        } else {
+           let t: TokenAsLiteral = input.parse()?;
+           Ok(TemplateCrumb::S(t.token_literal, t.span))
+        }
+    }
+}
+
+impl<D: Dialect> ToTokens for TemplateCrumb<D> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let mut e = Emitter::default();
+        self.emit(&mut e);
+        e.finish().to_tokens(tokens);
+    }
+}
+
+impl<D: Dialect> fmt::Display for TemplateCrumb<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+           TemplateCrumb::S(s,_) => write!(f, "{}", text_source(s)),
+           TemplateCrumb::T(t) => write!(f, "{}", t),
+           TemplateCrumb::E(e) => write!(f, "{}", e),
+           TemplateCrumb::F(b) => write!(f, "{}", b),
+           TemplateCrumb::C(c) => write!(f, "{}", c),
+           TemplateCrumb::D(d) => write!(f, "{}", d),
+           TemplateCrumb::M(m) => {
+              write!(f, "<!-- ")?;
+              m.fmt_text(f)?;
+              write!(f, " -->")
+           },
+           TemplateCrumb::N(n) => {
+              write!(f, "<!--- ")?;
+              n.fmt_text(f)?;
+              write!(f, " --->")
+           },
+           TemplateCrumb::G(g) => write!(f, "{}", g),
+        }
+    }
+}
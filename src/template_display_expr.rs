@@ -0,0 +1,117 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Span, Literal, TokenStream};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Ident, Token};
+use crate::template::Template;
+use crate::emit::Emitter;
+use crate::dialect::{Dialect,Output};
+
+pub enum TemplateDisplay<D> {
+   X(Template<D>)
+}
+impl<D: Dialect> TemplateDisplay<D> {
+   /// The expression building this markup as a `String`, serialized as `output`
+   /// or as the dialect's default.
+   pub(crate) fn tokens_with(&self, output: Option<Output>) -> TokenStream {
+      match self {
+         TemplateDisplay::X(templates) => {
+            let mut e = Emitter::default();
+            e.output = output;
+            templates.emit(&mut e);
+            if let Some(s) = e.static_output() {
+               let l = Literal::string(s);
+               return quote_spanned! { templates.span() =>
+                  String::from(#l)
+               };
+            }
+            let capacity = Literal::usize_unsuffixed(e.static_len());
+            let body = e.finish();
+            quote_spanned! { templates.span() =>
+               {
+                  let mut stream = String::with_capacity(#capacity);
+                  #body
+                  stream
+               }
+            }
+         }
+      }
+   }
+}
+impl<D: Dialect> ToTokens for TemplateDisplay<D> {
+   fn to_tokens(&self, tokens: &mut TokenStream) {
+      self.tokens_with(None).to_tokens(tokens);
+   }
+}
+
+/// Markup rendered to a `String`, `<?>…</?>`, or to a named slot, `<?header>…</?>`.
+pub struct TemplateDisplayExpr<D> {
+   pub open: Token![<],
+   pub slot: Option<Ident>,
+   pub expr: TemplateDisplay<D>,
+   pub close: Token![>],
+}
+impl<D: Dialect> TemplateDisplayExpr<D> {
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+}
+impl<D: Dialect> Parse for TemplateDisplayExpr<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _: Token![?] = input.parse()?;
+       let slot: Option<Ident> = if input.peek(Ident) { Some(input.parse()?) } else { None };
+       let _: Token![>] = input.parse()?;
+
+       let template: Template<D> = input.call(Template::parse_inner)?;
+       let expr = TemplateDisplay::X(template);
+
+       let _: Token![<] = input.parse()?;
+       let _: Token![/] = input.parse()?;
+       let _: Token![?] = input.parse()?;
+       if input.peek(Ident) {
+          let end: Ident = input.parse()?;
+          if slot.as_ref() != Some(&end) {
+             let open = slot.as_ref().map(|s| s.to_string()).unwrap_or_default();
+             let msg = format!("Expected </?{}> found </?{}>", open, end);
+             return Err(Error::new(end.span(), msg))
+          }
+       }
+       let close: Token![>] = input.parse()?;
+
+       Ok(TemplateDisplayExpr {
+          open,
+          slot,
+          expr,
+          close,
+       })
+    }
+}
+impl<D: Dialect> TemplateDisplayExpr<D> {
+    /// The expression building this markup as a `String`, serialized as `output`.
+    pub(crate) fn tokens_with(&self, output: Option<Output>) -> TokenStream {
+       self.expr.tokens_with(output)
+    }
+}
+impl<D: Dialect> ToTokens for TemplateDisplayExpr<D> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+       self.expr.to_tokens(tokens);
+    }
+}
+
+impl<D: Dialect> fmt::Display for TemplateDisplayExpr<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       match &self.expr {
+          TemplateDisplay::X(x) => match &self.slot {
+             Some(s) => write!(f, "<?{}>{}</?>", s, x),
+             None => write!(f, "<?>{}</?>", x),
+          }
+       }
+    }
+}
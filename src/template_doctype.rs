@@ -0,0 +1,62 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use proc_macro2::{Span, TokenTree};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::ext::IdentExt;
+use syn::{Ident, Token};
+use crate::emit::Emitter;
+
+/// A document type declaration, `<!DOCTYPE html>`.
+///
+/// The keyword is matched without regard to case, so a class can not be named `DOCTYPE`.
+pub struct TemplateDoctype {
+   pub keyword: String,
+   pub content: String,
+   pub span: Span,
+}
+
+impl TemplateDoctype {
+    /// Whether a doctype starts here, rather than a `<!Name>` class.
+    pub fn peek(input: ParseStream) -> bool {
+       let f = input.fork();
+       f.parse::<Token![<]>().is_ok() &&
+       f.parse::<Token![!]>().is_ok() &&
+       Ident::parse_any(&f).map(|i| i.to_string().eq_ignore_ascii_case("doctype")).unwrap_or(false)
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+       e.push_str(&self.to_string(), self.span);
+    }
+}
+
+impl Parse for TemplateDoctype {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _ex: Token![!] = input.parse()?;
+       let keyword = Ident::parse_any(input)?;
+       let mut content = Vec::new();
+       while !input.peek(Token![>]) {
+          let t: TokenTree = input.parse()?;
+          content.push(t.to_string());
+       }
+       let close: Token![>] = input.parse()?;
+       Ok(TemplateDoctype {
+          keyword: keyword.to_string(),
+          content: content.join(" "),
+          span: open.span.join(close.span).unwrap_or(open.span),
+       })
+    }
+}
+
+impl fmt::Display for TemplateDoctype {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       if !self.content.is_empty() {
+          write!(f, "<!{} {}>", self.keyword, self.content)
+       } else {
+          write!(f, "<!{}>", self.keyword)
+       }
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use quote::{quote_spanned, ToTokens};
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Token, Expr, Pat, braced};
+use syn::token::{Brace};
+use crate::template::TemplateCrumb;
+use crate::rust_parse::{parse_pat,parse_condition};
+use crate::emit::Emitter;
+use crate::unparse::rust_source;
+use crate::dialect::Dialect;
+
+pub enum TemplateExprInner<D> {
+   S(Expr),
+   E(Expr),
+   F(Token![for],Pat,Expr,Vec<TemplateCrumb<D>>),
+   W(Token![while],Expr,Vec<TemplateCrumb<D>>),
+   L(Token![let],Pat,Expr),
+   I(Token![if],Expr,Vec<TemplateCrumb<D>>,Vec<(Expr,Vec<TemplateCrumb<D>>)>,Vec<TemplateCrumb<D>>),
+   P(Token![loop],Vec<TemplateCrumb<D>>),
+   M(Token![match],Expr,Vec<(Pat,Option<Expr>,Vec<TemplateCrumb<D>>)>),
+}
+impl<D: Dialect> TemplateExprInner<D> {
+    pub fn does_emit(&self) -> bool {
+       match self {
+          TemplateExprInner::S(_) => { false },
+          TemplateExprInner::E(_) => { true },
+          TemplateExprInner::F(_,_,_,_) => { true },
+          TemplateExprInner::P(_,_) => { true },
+          TemplateExprInner::W(_,_,_) => { true },
+          TemplateExprInner::L(_,_,_) => { false },
+          TemplateExprInner::I(_,_,_,_,_) => { true },
+          TemplateExprInner::M(_,_,_) => { true },
+       }
+    }
+    fn emit_body(e: &mut Emitter, cs: &[TemplateCrumb<D>], span: Span) -> proc_macro2::TokenStream {
+        e.block(|e| {
+           for c in cs.iter() {
+              c.emit(e);
+           }
+           if D::WHITESPACE {
+              e.push_str(" ", span);
+           }
+        })
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+        match self {
+           TemplateExprInner::E(x) => {
+              if D::RENDER {
+                 e.push_render(x, syn::spanned::Spanned::span(x));
+              } else {
+                 e.push_display(x, syn::spanned::Spanned::span(x));
+              }
+           }, TemplateExprInner::S(x) => {
+              e.push_tokens(quote_spanned!{ syn::spanned::Spanned::span(x)=>
+                 #x;
+              });
+           }, TemplateExprInner::F(f,p,i,cs) => {
+              let cs = TemplateExprInner::emit_body(e, cs, f.span);
+              e.push_tokens(quote_spanned!{f.span=>
+                 for #p in #i { #cs }
+              });
+           }, TemplateExprInner::P(l,cs) => {
+              let cs = TemplateExprInner::emit_body(e, cs, l.span);
+              e.push_tokens(quote_spanned!{l.span=>
+                 loop { #cs }
+              });
+           }, TemplateExprInner::I(i,c,bs,es,x) => {
+              let bs = TemplateExprInner::emit_body(e, bs, i.span);
+              let mut ts = quote_spanned!{i.span=>
+                 if #c { #bs }
+              };
+
+              for (c,x) in es.iter() {
+                 let x = TemplateExprInner::emit_body(e, x, i.span);
+                 (quote_spanned!{i.span=>
+                    else if #c { #x }
+                 }).to_tokens(&mut ts);
+              }
+
+              if !x.is_empty() {
+                 let x = TemplateExprInner::emit_body(e, x, i.span);
+                 (quote_spanned!{i.span=>
+                    else { #x }
+                 }).to_tokens(&mut ts);
+              }
+              e.push_tokens(ts);
+           }, TemplateExprInner::W(w,i,cs) => {
+              let cs = TemplateExprInner::emit_body(e, cs, w.span);
+              e.push_tokens(quote_spanned!{w.span=>
+                 while #i { #cs }
+              });
+           }, TemplateExprInner::L(t,l,x) => {
+              e.push_tokens(quote_spanned!{t.span=>
+                 let #l = #x;
+              });
+           }, TemplateExprInner::M(m,x,arms) => {
+              let mut ts = proc_macro2::TokenStream::new();
+              for (p,g,cs) in arms.iter() {
+                 let g = g.as_ref().map(|g| quote_spanned!{m.span=> if #g });
+                 let cs = TemplateExprInner::emit_body(e, cs, m.span);
+                 (quote_spanned!{m.span=>
+                    #p #g => { #cs }
+                 }).to_tokens(&mut ts);
+              }
+              e.push_tokens(quote_spanned!{m.span=>
+                 match #x { #ts }
+              });
+           }
+        }
+    }
+}
+impl<D: Dialect> ToTokens for TemplateExprInner<D> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let mut e = Emitter::default();
+        self.emit(&mut e);
+        e.finish().to_tokens(tokens);
+    }
+}
+impl<D: Dialect> Parse for TemplateExprInner<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+       if input.peek(Token![for]) {
+          let _for: Token![for] = input.parse()?;
+          let pat: Pat = input.parse()?;
+          let _in: Token![in] = input.parse()?;
+          let iter: Expr = input.call(Expr::parse_without_eager_brace)?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<TemplateCrumb<D>> = content2.call(TemplateCrumb::parse_body)?;
+          Ok(TemplateExprInner::F(_for,pat,iter,body))
+       } else if input.peek(Token![loop]) {
+          let _loop: Token![loop] = input.parse()?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<TemplateCrumb<D>> = content2.call(TemplateCrumb::parse_body)?;
+          Ok(TemplateExprInner::P(_loop,body))
+       } else if input.peek(Token![while]) {
+          let _while: Token![while] = input.parse()?;
+          let iter: Expr = input.call(parse_condition)?;
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<TemplateCrumb<D>> = content2.call(TemplateCrumb::parse_body)?;
+          Ok(TemplateExprInner::W(_while,iter,body))
+       } else if input.peek(Token![if]) {
+          let _if: Token![if] = input.parse()?;
+          let b: Expr = input.call(parse_condition)?;
+          let mut es = Vec::new();
+          let mut e = Vec::new();
+          let content;
+          let content2;
+          let _brace1 = braced!(content in input);
+          let _brace2 = braced!(content2 in content);
+          let body: Vec<TemplateCrumb<D>> = content2.call(TemplateCrumb::parse_body)?;
+
+          while input.peek(Token![else]) && input.peek2(Token![if]) {
+             let _else: Token![else] = input.parse()?;
+             let _if: Token![if] = input.parse()?;
+             let b: Expr = input.call(parse_condition)?;
+             let content;
+             let content2;
+             let _brace1 = braced!(content in input);
+             let _brace2 = braced!(content2 in content);
+             let e = content2.call(TemplateCrumb::parse_body)?;
+             es.push((b,e));
+          }
+
+          if input.peek(Token![else]) {
+             let _else: Token![else] = input.parse()?;
+             let content;
+             let content2;
+             let _brace1 = braced!(content in input);
+             let _brace2 = braced!(content2 in content);
+             e = content2.call(TemplateCrumb::parse_body)?;
+          }
+
+          Ok(TemplateExprInner::I(_if,b,body,es,e))
+       } else if input.peek(Token![match]) {
+          let _match: Token![match] = input.parse()?;
+          let e: Expr = input.call(Expr::parse_without_eager_brace)?;
+          let content;
+          let _brace = braced!(content in input);
+          let mut arms = Vec::new();
+          while !content.is_empty() {
+             let pat = parse_pat(&content)?;
+             let guard = if content.peek(Token![if]) {
+                let _if: Token![if] = content.parse()?;
+                let g: Expr = content.parse()?;
+                Some(g)
+             } else { None };
+             let _arrow: Token![=>] = content.parse()?;
+             let arm;
+             let arm2;
+             let _brace1 = braced!(arm in content);
+             let _brace2 = braced!(arm2 in arm);
+             let body: Vec<TemplateCrumb<D>> = arm2.call(TemplateCrumb::parse_body)?;
+             arms.push((pat,guard,body));
+             if content.peek(Token![,]) {
+                let _comma: Token![,] = content.parse()?;
+             }
+          }
+          Ok(TemplateExprInner::M(_match,e,arms))
+       } else if input.peek(Token![let]) {
+          let _let: Token![let] = input.parse()?;
+          let pat: Pat = input.parse()?;
+          let _eq: Token![=] = input.parse()?;
+          let expr: Expr = input.parse()?;
+          if input.peek(Token![;]) {
+             let _: Token![;] = input.parse()?;
+          }
+          Ok(TemplateExprInner::L(_let,pat,expr))
+       } else {
+          let e: Expr = input.parse()?;
+          if input.peek(Token![;]) {
+             let _semi: Token![;] = input.parse()?;
+             Ok(TemplateExprInner::S(e))
+          } else {
+             Ok(TemplateExprInner::E(e))
+          }
+       }
+    }
+}
+
+pub struct TemplateExpr<D> {
+   pub brace_token1: Brace,
+   pub brace_token2: Brace,
+   pub expr: TemplateExprInner<D>
+}
+impl<D: Dialect> TemplateExpr<D> {
+    pub fn does_emit(&self) -> bool {
+       self.expr.does_emit()
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+       self.expr.emit(e)
+    }
+}
+impl<D: Dialect> Parse for TemplateExpr<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let _content;
+        let content2;
+        Ok(TemplateExpr {
+           brace_token1: braced!(_content in input),
+           brace_token2: braced!(content2 in _content),
+           expr: content2.call(TemplateExprInner::parse)?,
+        })
+    }
+}
+impl<D: Dialect> ToTokens for TemplateExpr<D> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.expr.to_tokens(tokens)
+    }
+}
+
+
+fn fmt_body<D: Dialect>(cs: &[TemplateCrumb<D>], f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{{{{ ")?;
+    TemplateCrumb::fmt_crumbs(cs, f)?;
+    write!(f, " }}}}")
+}
+impl<D: Dialect> fmt::Display for TemplateExprInner<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+           TemplateExprInner::E(e) => {
+              write!(f, "{}", rust_source(e))
+           }, TemplateExprInner::S(e) => {
+              write!(f, "{};", rust_source(e))
+           }, TemplateExprInner::F(_,p,i,cs) => {
+              write!(f, "for {} in {} ", rust_source(p), rust_source(i))?;
+              fmt_body(cs, f)
+           }, TemplateExprInner::P(_,cs) => {
+              write!(f, "loop ")?;
+              fmt_body(cs, f)
+           }, TemplateExprInner::I(_,c,bs,es,e) => {
+              write!(f, "if {} ", rust_source(c))?;
+              fmt_body(bs, f)?;
+              for (c,e) in es.iter() {
+                 write!(f, " else if {} ", rust_source(c))?;
+                 fmt_body(e, f)?;
+              }
+              if !e.is_empty() {
+                 write!(f, " else ")?;
+                 fmt_body(e, f)?;
+              }
+              Ok(())
+           }, TemplateExprInner::W(_,c,cs) => {
+              write!(f, "while {} ", rust_source(c))?;
+              fmt_body(cs, f)
+           }, TemplateExprInner::L(_,p,e) => {
+              write!(f, "let {} = {};", rust_source(p), rust_source(e))
+           }, TemplateExprInner::M(_,e,arms) => {
+              write!(f, "match {} {{", rust_source(e))?;
+              for (p,g,cs) in arms.iter() {
+                 write!(f, " {}", rust_source(p))?;
+                 if let Some(g) = g {
+                    write!(f, " if {}", rust_source(g))?;
+                 }
+                 write!(f, " => ")?;
+                 fmt_body(cs, f)?;
+                 write!(f, ",")?;
+              }
+              write!(f, " }}")
+           }
+        }
+    }
+}
+impl<D: Dialect> fmt::Display for TemplateExpr<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{{{ {} }}}}", self.expr)
+    }
+}
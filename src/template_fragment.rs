@@ -0,0 +1,71 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use quote::{ToTokens};
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result, Error};
+use syn::{Token};
+use crate::template::{Template,TemplateName};
+use crate::emit::Emitter;
+use crate::dialect::Dialect;
+
+/// A group of sibling crumbs, `<>…</>`, that writes only its children.
+pub struct TemplateFragment<D> {
+   pub open: Token![<],
+   pub inner: Template<D>,
+   pub close: Token![>],
+}
+
+impl<D: Dialect> TemplateFragment<D> {
+    /// Whether a fragment starts here.
+    pub fn peek(input: ParseStream) -> bool {
+       input.peek(Token![<]) && input.peek2(Token![>])
+    }
+    pub fn span(&self) -> Span {
+       self.open.span.join(self.close.span).unwrap_or(self.open.span)
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+       self.inner.emit(e);
+    }
+}
+
+impl<D: Dialect> Parse for TemplateFragment<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let open: Token![<] = input.parse()?;
+       let _gt: Token![>] = input.parse()?;
+       let inner: Template<D> = input.call(Template::parse_inner)?;
+       if input.is_empty() {
+          return Err(Error::new(open.span, "Unclosed fragment <>"))
+       }
+       let _lt: Token![<] = input.parse()?;
+       let _slash: Token![/] = input.parse()?;
+       if !input.peek(Token![>]) {
+          let t: TemplateName = input.parse()?;
+          let msg = format!("Expected </> found </{}>", t);
+          return Err(Error::new(t.span, msg))
+       }
+       let close: Token![>] = input.parse()?;
+       Ok(TemplateFragment {
+          open,
+          inner,
+          close,
+       })
+    }
+}
+
+impl<D: Dialect> ToTokens for TemplateFragment<D> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+       let mut e = Emitter::default();
+       self.emit(&mut e);
+       e.finish().to_tokens(tokens);
+    }
+}
+
+impl<D: Dialect> fmt::Display for TemplateFragment<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       write!(f, "<>{}</>", self.inner)
+    }
+}
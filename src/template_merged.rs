@@ -0,0 +1,171 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use quote::{format_ident, quote_spanned, ToTokens};
+use proc_macro2::{Span, TokenStream};
+use syn::parse::{Result, Error};
+use syn::{Lit};
+use crate::template::{TemplateTag,TemplateAttrKey,TemplateAttr,TemplateExpr,TemplateExprInner,TemplateName};
+use crate::runtime::AttributeList;
+use crate::emit::Emitter;
+use crate::dialect::Dialect;
+
+/// Attributes whose contributions are merged into one value.
+///
+/// `class="btn" class:active={{ on }}` writes a single `class` attribute, as does
+/// `style="margin:0" style:color={{ c }}` for `style`. An attribute in a namespace
+/// bound to the prefix `class` or `style` is written with a string key, `"class:x"=…`.
+const MERGED: [&str; 2] = ["class", "style"];
+
+/// The merged attribute that `k` contributes to, if any.
+pub fn merged_into(k: &TemplateName) -> Option<&'static str> {
+   let name = k.prefix.as_ref().unwrap_or(&k.local);
+   MERGED.iter().find(|m| **m == name).copied()
+}
+
+/// The merged attribute whose name `k` binds as a namespace prefix, as `xmlns:class` does.
+pub(crate) fn declared_prefix(k: &TemplateName) -> Option<&'static str> {
+   if k.prefix.as_deref() != Some("xmlns") { return None }
+   MERGED.iter().find(|m| **m == k.local).copied()
+}
+
+/// The error for a contribution `k` whose prefix is also declared as a namespace.
+pub(crate) fn prefix_conflict(k: &TemplateName) -> Error {
+   let msg = format!("{} is merged into {}, but the prefix is also a namespace; write a namespaced attribute as \"{}\"", k, k.prefix.as_deref().unwrap_or(""), k);
+   Error::new(k.span, msg)
+}
+
+fn key_name(k: &TemplateAttrKey) -> Option<&TemplateName> {
+   match k {
+      TemplateAttrKey::S(k) => Some(k),
+      TemplateAttrKey::G(_,k) => Some(k),
+      TemplateAttrKey::P(_,_) => None,
+   }
+}
+
+fn single_expr<D>(v: &Option<TemplateAttr<D>>) -> bool {
+   matches!(v, Some(TemplateAttr::E(TemplateExpr { expr: TemplateExprInner::E(_), .. })))
+}
+
+/// Check that each contribution to `class` or `style` can be merged.
+pub fn check_merged<D: Dialect>(t: &TemplateTag<D>) -> Result<()> {
+   let declared: Vec<&str> = t.attrs.iter().filter_map(|(k,_)| key_name(k).and_then(declared_prefix)).collect();
+   for (k,v) in t.attrs.iter() {
+      let k = match key_name(k) { Some(k) => k, None => continue };
+      if k.prefix.is_some() && matches!(merged_into(k), Some(m) if declared.contains(&m)) {
+         return Err(prefix_conflict(k))
+      }
+      let ok = match (merged_into(k), &k.prefix) {
+         (None, _) => true,
+         (Some(_), None) => matches!(v, Some(TemplateAttr::S(_)) | Some(TemplateAttr::F(_))) || single_expr(v),
+         (Some("class"), Some(_)) => v.is_none() || single_expr(v),
+         (Some(_), Some(_)) => matches!(v, Some(TemplateAttr::S(_))) || single_expr(v),
+      };
+      if !ok {
+         let msg = match (merged_into(k), &k.prefix) {
+            (Some("class"), Some(_)) => format!("{} takes a {{{{ condition }}}} or no value", k),
+            (Some(m), Some(_)) => format!("{} takes a string or a {{{{ expr }}}}", m),
+            _ => format!("{} takes a string, a {{{{ expr }}}} or a [[ expr ]]", k),
+         };
+         return Err(Error::new(k.span, msg))
+      }
+   }
+   Ok(())
+}
+
+/// The value of a static attribute, as stored by `Dialect::quote_attribute`, before quoting.
+pub(crate) fn static_value<D: Dialect>(s: &str) -> String {
+   match syn::parse_str::<Lit>(&D::attribute_source(s)) {
+      Ok(Lit::Str(l)) => l.value(),
+      Ok(Lit::Char(l)) => l.value().to_string(),
+      _ => s.to_string(),
+   }
+}
+
+/// The statement adding one contribution to the list, or its static item.
+fn contribution<D: Dialect>(k: &TemplateName, v: &Option<TemplateAttr<D>>, span: Span) -> (TokenStream, Option<(Option<String>,String)>) {
+   match (&k.prefix, v) {
+      (None, Some(TemplateAttr::S(s))) => {
+         let s = static_value::<D>(s);
+         (quote_spanned!{span=> attr_list.push(#s); }, Some((None, s)))
+      },
+      (None, Some(TemplateAttr::F(f))) => {
+         let expr = &f.expr;
+         let coerce = format_ident!("to_{}", k.local, span=f.bracket.span);
+         (quote_spanned!{span=> attr_list.push(&#expr.#coerce()); }, None)
+      },
+      (None, Some(TemplateAttr::E(TemplateExpr { expr: TemplateExprInner::E(x), .. }))) => {
+         (quote_spanned!{span=> attr_list.push(&(#x)); }, None)
+      },
+      (Some(p), None) if p == "class" => {
+         let l = &k.local;
+         (quote_spanned!{span=> attr_list.push(#l); }, Some((None, l.clone())))
+      },
+      (Some(p), Some(TemplateAttr::E(TemplateExpr { expr: TemplateExprInner::E(x), .. }))) if p == "class" => {
+         let l = &k.local;
+         (quote_spanned!{span=> if #x { attr_list.push(#l); } }, None)
+      },
+      (Some(_), Some(TemplateAttr::S(s))) => {
+         let l = &k.local;
+         let s = static_value::<D>(s);
+         (quote_spanned!{span=> attr_list.push_property(#l, #s); }, Some((Some(l.clone()), s)))
+      },
+      (Some(_), Some(TemplateAttr::E(TemplateExpr { expr: TemplateExprInner::E(x), .. }))) => {
+         let l = &k.local;
+         (quote_spanned!{span=> attr_list.push_property(#l, &(#x)); }, None)
+      },
+      _ => (quote_spanned!{span=> compile_error!("this attribute can not be merged"); }, None),
+   }
+}
+
+/// Emit the merged attribute `name` with every contribution to it from the tag.
+pub(crate) fn emit_merged<D: Dialect>(t: &TemplateTag<D>, e: &mut Emitter, name: &str) {
+   let span = t.outer_span;
+   let mut list = if name == "class" { AttributeList::classes() } else { AttributeList::styles() };
+   let mut stmts = TokenStream::new();
+   let mut is_static = true;
+   for (i,(k,v)) in t.attrs.iter().enumerate() {
+      match k {
+         TemplateAttrKey::S(k) if merged_into(k) == Some(name) => {
+            let (stmt, item) = contribution(k, v, span);
+            match item {
+               Some((None, s)) => list.push(&s),
+               Some((Some(p), s)) => list.push_property(&p, &s),
+               None => { is_static = false; },
+            }
+            stmt.to_tokens(&mut stmts);
+         },
+         TemplateAttrKey::G(g,k) if merged_into(k) == Some(name) => {
+            let (stmt, _) = contribution(k, v, span);
+            let g = t.guard(i, g);
+            is_static = false;
+            (quote_spanned!{span=> if #g { #stmt } }).to_tokens(&mut stmts);
+         },
+         _ => {}
+      }
+   }
+
+   if is_static {
+      if !list.is_empty() {
+         e.push_str(&format!(" {}=\"", name), span);
+         e.escaped(D::ATTRIBUTE, |e| e.push_str(list.as_str(), span));
+         e.push_str("\"", span);
+      }
+   } else {
+      let body = e.block(|e| {
+         e.push_str(&format!(" {}=\"", name), span);
+         e.escaped(D::ATTRIBUTE, |e| e.push_display(quote_spanned!{span=> attr_list }, span));
+         e.push_str("\"", span);
+      });
+      let new = if name == "class" { quote_spanned!{span=> classes } } else { quote_spanned!{span=> styles } };
+      e.push_tokens(quote_spanned!{span=>
+         {
+            let mut attr_list = ::rdxl_internals::runtime::AttributeList::#new();
+            #stmts
+            if !attr_list.is_empty() { #body }
+         }
+      });
+   }
+}
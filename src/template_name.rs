@@ -0,0 +1,128 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::ext::IdentExt;
+use syn::{Ident, Token};
+use crate::unparse::name_source;
+
+/// A tag or attribute name, optionally qualified by a namespace prefix as in `xlink:href`.
+#[derive(Clone, Debug)]
+pub struct TemplateName {
+   pub prefix: Option<String>,
+   pub local: String,
+   pub span: Span,
+}
+
+impl TemplateName {
+    pub fn new(prefix: Option<String>, local: String, span: Span) -> TemplateName {
+       TemplateName {
+          prefix,
+          local,
+          span,
+       }
+    }
+    /// The name as written in markup, `prefix:local` or `local`.
+    pub fn qualified(&self) -> String {
+       self.to_string()
+    }
+    /// Template source for this name.
+    pub(crate) fn source(&self) -> String {
+       match &self.prefix {
+          Some(p) => format!("{}:{}", name_source(p), name_source(&self.local)),
+          None => name_source(&self.local),
+       }
+    }
+}
+
+impl From<&str> for TemplateName {
+    /// Split a qualified name at its first `:`.
+    fn from(s: &str) -> TemplateName {
+       match s.find(':') {
+          Some(i) => TemplateName::new(Some(s[..i].to_string()), s[i+1..].to_string(), Span::call_site()),
+          None => TemplateName::new(None, s.to_string(), Span::call_site()),
+       }
+    }
+}
+
+impl PartialEq for TemplateName {
+    fn eq(&self, other: &TemplateName) -> bool {
+       self.prefix == other.prefix && self.local == other.local
+    }
+}
+impl Eq for TemplateName {}
+
+impl PartialEq<str> for TemplateName {
+    fn eq(&self, other: &str) -> bool {
+       match &self.prefix {
+          Some(p) => other.len() == p.len() + 1 + self.local.len() &&
+                     other.starts_with(p.as_str()) &&
+                     other[p.len()..].starts_with(':') &&
+                     other.ends_with(self.local.as_str()),
+          None => self.local == other,
+       }
+    }
+}
+impl<'a> PartialEq<&'a str> for TemplateName {
+    fn eq(&self, other: &&'a str) -> bool {
+       *self == **other
+    }
+}
+
+impl fmt::Display for TemplateName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+       match &self.prefix {
+          Some(p) => write!(f, "{}:{}", p, self.local),
+          None => write!(f, "{}", self.local),
+       }
+    }
+}
+
+impl Parse for TemplateName {
+    fn parse(input: ParseStream) -> Result<Self> {
+       let (first, span) = parse_part(input)?;
+       if input.peek(Token![:]) && !input.peek(Token![::]) && input.peek2(Ident::peek_any) {
+          let _colon: Token![:] = input.parse()?;
+          let (local, end) = parse_part(input)?;
+          Ok(TemplateName::new(Some(first), local, span.join(end).unwrap_or(span)))
+       } else {
+          Ok(TemplateName::new(None, first, span))
+       }
+    }
+}
+
+/// Whether `a` and `b` are the same html element or attribute name: names without
+/// a namespace prefix compare without case, namespaced names exactly.
+pub fn same_name(a: &TemplateName, b: &TemplateName) -> bool {
+   match (&a.prefix, &b.prefix) {
+      (None, None) => a.local.eq_ignore_ascii_case(&b.local),
+      _ => a == b,
+   }
+}
+
+/// Whether a tag or attribute name starts here.
+pub fn peek_name(input: ParseStream) -> bool {
+   input.peek(Ident::peek_any)
+}
+
+/// Parse one part of a name, `ident(-ident)*`.
+///
+/// Keywords are accepted in every part, so `for`, `type` and `http-equiv`
+/// are all names. The span covers the whole part where spans can be joined.
+fn parse_part(input: ParseStream) -> Result<(String, Span)> {
+   let first = Ident::parse_any(input)?;
+   let mut name = first.to_string();
+   let mut span = first.span();
+   while input.peek(Token![-]) && input.peek2(Ident::peek_any) {
+      let _dash: Token![-] = input.parse()?;
+      let part = Ident::parse_any(input)?;
+      name.push('-');
+      name.push_str(&part.to_string());
+      span = span.join(part.span()).unwrap_or(span);
+   }
+   Ok((name, span))
+}
@@ -0,0 +1,156 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use proc_macro2::{Span, TokenStream, TokenTree, Delimiter, Spacing};
+use syn::parse::{ParseStream, Result, Error};
+use syn::{Token};
+use crate::template::{TemplateCrumb,TemplateExpr,TemplateExprInner,TemplateName};
+use crate::template_name::same_name;
+use crate::unparse::needs_space;
+use crate::dialect::Dialect;
+use crate::html::{element_kind,ElementKind};
+
+/// Whether the content of `name` is parsed as text rather than as crumbs.
+///
+/// Escapable raw text, `<textarea>`, is parsed as crumbs and only checked for child tags.
+pub fn is_raw_text(name: &TemplateName) -> bool {
+   element_kind(name) == ElementKind::RawText
+}
+
+/// Parse the content of a raw text element up to its closing tag.
+///
+/// The text is rebuilt from the tokens, with the whitespace between them
+/// recovered from their spans. Where spans carry no positions, a space is
+/// written only where tokens would otherwise run together. `{{ expr }}` is
+/// the only form of interpolation, and only in `<script>`, where the value is
+/// written as a string literal; anything else in braces is text.
+pub fn parse_raw<D: Dialect>(input: ParseStream, name: &TemplateName, open: Span) -> Result<Vec<TemplateCrumb<D>>> {
+   let mut r = RawText {
+      name: name.clone(),
+      crumbs: Vec::new(),
+      text: String::new(),
+      text_span: None,
+      last: open,
+      last_text: String::new(),
+      joint: false,
+      punct: false,
+   };
+   while !input.is_empty() && !closes(input, name) {
+      let tt: TokenTree = input.parse()?;
+      r.push_tree(tt)?;
+   }
+   if let Ok(lt) = input.fork().parse::<Token![<]>() {
+      r.push_whitespace(lt.span, "<", false);
+   }
+   r.flush();
+   Ok(r.crumbs)
+}
+
+fn closes(input: ParseStream, name: &TemplateName) -> bool {
+   let f = input.fork();
+   f.parse::<Token![<]>().is_ok() &&
+   f.parse::<Token![/]>().is_ok() &&
+   f.parse::<TemplateName>().map(|n| same_name(&n, name)).unwrap_or(false)
+}
+
+struct RawText<D> {
+   name: TemplateName,
+   crumbs: Vec<TemplateCrumb<D>>,
+   text: String,
+   text_span: Option<Span>,
+   last: Span,
+   last_text: String,
+   joint: bool,
+   /// Whether the last token was punctuation, which `joint` then tells apart from the next.
+   punct: bool,
+}
+
+impl<D: Dialect> RawText<D> {
+   fn push_whitespace(&mut self, span: Span, next: &str, punct: bool) {
+      let (a, b) = (self.last.end(), span.start());
+      // Tokens made by a macro, rather than read from source, have empty spans.
+      if a.line == 0 || b.line == 0 || span.end() == b {
+         if !self.joint && ((self.punct && punct) || needs_space(&self.last_text, next)) {
+            self.text.push(' ');
+         }
+      } else if b.line > a.line {
+         self.text.push_str(&"\n".repeat(b.line - a.line));
+         self.text.push_str(&" ".repeat(b.column));
+      } else if b.column > a.column {
+         self.text.push_str(&" ".repeat(b.column - a.column));
+      }
+   }
+   fn push_text(&mut self, s: &str, span: Span, joint: bool, punct: bool) {
+      self.push_whitespace(span, s, punct);
+      if self.text_span.is_none() {
+         self.text_span = Some(span);
+      }
+      self.text.push_str(s);
+      self.last = span;
+      self.last_text = s.to_string();
+      self.joint = joint;
+      self.punct = punct;
+   }
+   fn flush(&mut self) {
+      if let Some(span) = self.text_span.take() {
+         let text = std::mem::take(&mut self.text);
+         self.crumbs.push(TemplateCrumb::S(text, span));
+      }
+   }
+   fn push_stream(&mut self, ts: TokenStream) -> Result<()> {
+      for tt in ts {
+         self.push_tree(tt)?;
+      }
+      Ok(())
+   }
+   fn push_tree(&mut self, tt: TokenTree) -> Result<()> {
+      match tt {
+         TokenTree::Group(g) => {
+            if g.delimiter() == Delimiter::Brace && is_interpolation(g.stream()) {
+               let e: TemplateExpr<D> = syn::parse2(TokenTree::Group(g.clone()).into())?;
+               if let TemplateExprInner::E(_) = e.expr {} else {
+                  return Err(Error::new(g.span(), "Only {{ expr }} can be interpolated into raw text"));
+               }
+               if !self.name.local.eq_ignore_ascii_case("script") {
+                  let msg = format!("{{{{ expr }}}} can not be interpolated into <{}>, only into <script> as a string", self.name);
+                  return Err(Error::new(g.span(), msg));
+               }
+               self.push_whitespace(g.span_open(), "{", false);
+               self.flush();
+               self.crumbs.push(TemplateCrumb::E(e));
+               self.last = g.span_close();
+               self.last_text = "}".to_string();
+               self.joint = false;
+               self.punct = false;
+               return Ok(())
+            }
+            let (open, close) = match g.delimiter() {
+               Delimiter::Parenthesis => ("(", ")"),
+               Delimiter::Brace => ("{", "}"),
+               Delimiter::Bracket => ("[", "]"),
+               Delimiter::None => return self.push_stream(g.stream()),
+            };
+            self.push_text(open, g.span_open(), false, false);
+            self.push_stream(g.stream())?;
+            self.push_text(close, g.span_close(), false, false);
+         },
+         TokenTree::Punct(p) => {
+            self.push_text(&p.to_string(), p.span(), p.spacing() == Spacing::Joint, true);
+         },
+         tt => {
+            self.push_text(&tt.to_string(), tt.span(), false, false);
+         }
+      }
+      Ok(())
+   }
+}
+
+fn is_interpolation(ts: TokenStream) -> bool {
+   let mut it = ts.into_iter();
+   match (it.next(), it.next()) {
+      (Some(TokenTree::Group(g)), None) => g.delimiter() == Delimiter::Brace,
+      _ => false
+   }
+}
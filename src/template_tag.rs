@@ -0,0 +1,416 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use quote::{format_ident, quote_spanned, ToTokens};
+use proc_macro2::{Span};
+use syn::parse::{Parse, ParseStream, Result};
+use syn::{Token, Expr, LitStr, braced};
+use syn::token::{Brace};
+
+use crate::template::{TemplateAttr,Template,TemplateCrumb,TemplateDisplayExpr,TemplateExpr,TemplateExprInner};
+use crate::template_name::{TemplateName,peek_name};
+use crate::rust_parse::parse_condition;
+use crate::emit::{Emitter,Escape};
+use crate::template_raw::{is_raw_text,parse_raw};
+use crate::template_component::{is_component,check_component,emit_component,parse_children};
+use crate::template_merged::{merged_into,check_merged,emit_merged,static_value};
+use crate::unparse::{rust_source,gap};
+use crate::dialect::{Dialect,Output};
+use crate::html::{element_kind,ElementKind};
+use crate::template_validate::validate_tag;
+
+pub enum TemplateAttrKey {
+   S(TemplateName),
+   G(Expr,TemplateName),
+   /// A spread of any `IntoIterator<Item=(K,V)>`, `{{..attrs}}`.
+   P(Expr,Span)
+}
+
+/// How a tag ends.
+pub enum TemplateClose {
+   /// `<x/>`.
+   S,
+   /// `</y>`, which should name the tag it closes.
+   C(TemplateName),
+   /// The input ends before the tag is closed.
+   U,
+}
+
+pub struct TemplateTag<D> {
+   pub tag: TemplateName,
+   pub attrs: Vec<(TemplateAttrKey,Option<TemplateAttr<D>>)>,
+   pub inner: Template<D>,
+   /// The named slots `<?header>…</?>` of a component, which fill its fields.
+   pub slots: Vec<TemplateDisplayExpr<D>>,
+   pub close: TemplateClose,
+   pub outer_span: Span,
+   pub inner_span_start: Span,
+   pub inner_span_end: Span,
+}
+
+impl<D: Dialect> TemplateTag<D> {
+    fn emit_attr(&self, e: &mut Emitter, k: &TemplateName, v: &Option<TemplateAttr<D>>) {
+        match v {
+           None if D::minimize(e.output::<D>()) => {
+              e.push_str(&format!(" {}", k), self.outer_span);
+           }, None => {
+              e.push_str(&format!(" {}=\"{}\"", k, k), self.outer_span);
+           }, Some(TemplateAttr::S(s)) if s.starts_with(['"', '\'']) && e.output::<D>() != Output::Xml => {
+              e.push_str(&format!(" {}={}", k, s), self.outer_span);
+           }, Some(TemplateAttr::S(s)) => {
+              // Numbers and booleans are stored unquoted, and string values are
+              // escaped again with numeric references for xml.
+              e.push_str(&format!(" {}=\"", k), self.outer_span);
+              e.escaped(D::ATTRIBUTE, |e| e.push_str(&static_value::<D>(s), self.outer_span));
+              e.push_str("\"", self.outer_span);
+           }, Some(TemplateAttr::F(f)) if D::RENDER => {
+              let expr = &f.expr;
+              let coerce = format_ident!("to_{}", f.context, span=f.bracket.span);
+              self.emit_typed(e, k, quote_spanned!{f.bracket.span=> #expr.#coerce() });
+           }, Some(TemplateAttr::E(TemplateExpr { expr: TemplateExprInner::E(x), .. })) if D::RENDER => {
+              self.emit_typed(e, k, x.to_token_stream());
+           }, Some(TemplateAttr::F(f)) => {
+              e.push_str(&format!(" {}=\"", k), self.outer_span);
+              e.escaped(D::ATTRIBUTE, |e| f.emit(e));
+              e.push_str("\"", self.outer_span);
+           }, Some(TemplateAttr::E(x)) => {
+              e.push_str(&format!(" {}=\"", k), self.outer_span);
+              e.escaped(D::ATTRIBUTE, |e| x.emit(e));
+              e.push_str("\"", self.outer_span);
+           }, Some(TemplateAttr::G(g)) => {
+              e.push_str(&format!(" {}=\"", k), self.outer_span);
+              e.escaped(D::ATTRIBUTE, |e| g.emit(e));
+              e.push_str("\"", self.outer_span);
+           }
+        }
+    }
+    /// Write a dynamic attribute with `runtime::AttributeValue`, which may omit it.
+    fn emit_typed(&self, e: &mut Emitter, k: &TemplateName, value: proc_macro2::TokenStream) {
+        let span = self.outer_span;
+        let name = k.to_string();
+        let minimize = D::minimize(e.output::<D>());
+        let sink = e.sink(span);
+        e.push_tokens(quote_spanned!{span=>
+           {
+              #[allow(unused_imports)]
+              use ::rdxl_internals::runtime::{AttributeValue as _, DisplayAttributeValue as _};
+              (&(#value)).push_attribute(#name, #minimize, #sink);
+           }
+        });
+    }
+    fn has_spread(&self) -> bool {
+        self.attrs.iter().any(|(k,_)| matches!(k, TemplateAttrKey::P(_,_)))
+    }
+    /// The condition of the guarded attribute at `i`, `{{ if c {{ k=v }} }}`.
+    ///
+    /// A tag with a spread evaluates its guards once, into `attr_guard_i`, so that
+    /// the spread can also leave out the keys whose guard held.
+    pub(crate) fn guard(&self, i: usize, g: &Expr) -> proc_macro2::TokenStream {
+        if self.has_spread() {
+           format_ident!("attr_guard_{}", i, span=self.outer_span).into_token_stream()
+        } else {
+           g.to_token_stream()
+        }
+    }
+    fn emit_spread(&self, e: &mut Emitter, attrs: &Expr, span: Span) {
+        let name = |k: &TemplateName| merged_into(k).map(|m| m.to_string()).unwrap_or(k.to_string());
+        let written: Vec<String> = self.attrs.iter().filter_map(|(k,_)| match k {
+           TemplateAttrKey::S(k) => Some(name(k)),
+           _ => None
+        }).collect();
+        let mut guarded = proc_macro2::TokenStream::new();
+        for (i,(k,_)) in self.attrs.iter().enumerate() {
+           if let TemplateAttrKey::G(g,k) = k {
+              let g = self.guard(i, g);
+              let k = name(k);
+              (quote_spanned!{span=> if #g { attr_written.push(#k); } }).to_tokens(&mut guarded);
+           }
+        }
+        let body = e.block(|e| {
+           e.push_str(" ", span);
+           let sink = e.sink(span);
+           e.push_tokens(quote_spanned!{span=>
+              ::rdxl_internals::runtime::Sink::push_str(#sink, ::std::convert::AsRef::<str>::as_ref(&attr_key));
+           });
+           e.push_str("=\"", span);
+           e.escaped(D::ATTRIBUTE, |e| if D::RENDER {
+              e.push_render(quote_spanned!{span=> attr_value }, span);
+           } else {
+              e.push_display(quote_spanned!{span=> attr_value }, span);
+           });
+           e.push_str("\"", span);
+        });
+        if guarded.is_empty() {
+           e.push_tokens(quote_spanned!{span=>
+              for (attr_key, attr_value) in ::rdxl_internals::runtime::spread_attributes(#attrs, &[#(#written),*]) { #body }
+           });
+        } else {
+           e.push_tokens(quote_spanned!{span=>
+              {
+                 let mut attr_written: ::std::vec::Vec<&str> = ::std::vec![#(#written),*];
+                 #guarded
+                 for (attr_key, attr_value) in ::rdxl_internals::runtime::spread_attributes(#attrs, &attr_written) { #body }
+              }
+           });
+        }
+    }
+    /// Report every structural error in this tag and its children, combined into one error.
+    pub fn validate(&self) -> Result<()> {
+        validate_tag(self)
+    }
+    /// Whether this tag is a component, `<Card>`, rather than an element.
+    pub fn is_component(&self) -> bool {
+        is_component(&self.tag)
+    }
+    pub(crate) fn emit(&self, e: &mut Emitter) {
+        e.output.get_or_insert(D::OUTPUT);
+        if self.is_component() {
+           emit_component(self, e);
+           return;
+        }
+        if self.has_spread() {
+           for (i,(k,_)) in self.attrs.iter().enumerate() {
+              if let TemplateAttrKey::G(g,_) = k {
+                 let guard = self.guard(i, g);
+                 e.push_tokens(quote_spanned!{self.outer_span=> let #guard: bool = #g; });
+              }
+           }
+        }
+        e.push_str(&format!("<{}", self.tag), self.outer_span);
+
+        let mut merged: Vec<&str> = Vec::new();
+        for (i,(k,v)) in self.attrs.iter().enumerate() {
+            match k {
+               TemplateAttrKey::S(k) | TemplateAttrKey::G(_,k) if merged_into(k).is_some() => {
+                  let m = merged_into(k).unwrap();
+                  if !merged.contains(&m) {
+                     merged.push(m);
+                     emit_merged(self, e, m);
+                  }
+               }, TemplateAttrKey::S(k) => {
+                  self.emit_attr(e, k, v);
+               }, TemplateAttrKey::G(g,k) => {
+                  let g = self.guard(i, g);
+                  let a = e.block(|e| self.emit_attr(e, k, v));
+                  e.push_tokens(quote_spanned!{self.outer_span=>
+                     if #g { #a }
+                  });
+               }, TemplateAttrKey::P(p,span) => {
+                  self.emit_spread(e, p, *span);
+               }
+            }
+        }
+
+        let kind = element_kind(&self.tag);
+        let output = e.output::<D>();
+        if kind == ElementKind::Void && self.inner.crumbs.is_empty() {
+           match output {
+              Output::Html5 => e.push_str(">", self.outer_span),
+              Output::Xhtml | Output::Xml => e.push_str("/>", self.outer_span),
+           }
+        } else if output == Output::Xml && self.inner.crumbs.is_empty() {
+           e.push_str("/>", self.outer_span);
+        } else if kind == ElementKind::RawText {
+           e.push_str(">", self.outer_span);
+           for c in self.inner.crumbs.iter() {
+              match c {
+                 TemplateCrumb::E(x) => {
+                    e.push_str("\"", self.outer_span);
+                    e.escaped(Escape::Script, |e| x.emit(e));
+                    e.push_str("\"", self.outer_span);
+                 },
+                 c => c.emit(e),
+              }
+           }
+           e.push_str(&format!("</{}>", self.tag), self.outer_span);
+        } else {
+           e.push_str(">", self.outer_span);
+
+           if D::WHITESPACE && !self.inner.crumbs.is_empty() && self.inner.span().start() > self.inner_span_start.end() {
+              e.push_str(" ", self.outer_span);
+           }
+
+           self.inner.emit(e);
+
+           if D::WHITESPACE && !self.inner.crumbs.is_empty() && self.inner.span().end() < self.inner_span_end.start() {
+              e.push_str(" ", self.outer_span);
+           }
+
+           e.push_str(&format!("</{}>", self.tag), self.outer_span);
+        }
+    }
+}
+
+impl<D: Dialect> ToTokens for TemplateTag<D> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let mut e = Emitter::default();
+        self.emit(&mut e);
+        e.finish().to_tokens(tokens);
+    }
+}
+
+impl<D: Dialect> fmt::Display for TemplateTag<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}", self.tag.source())?;
+        for (k,v) in self.attrs.iter() {
+            match k {
+               TemplateAttrKey::S(k) => {
+                  write!(f, " {}", k.source())?;
+                  if let Some(v) = v { write!(f, "={}", v)?; }
+               }, TemplateAttrKey::G(g,k) => {
+                  write!(f, " {{{{ if {} {{{{ {}", rust_source(g), k.source())?;
+                  if let Some(v) = v { write!(f, "={}", v)?; }
+                  write!(f, " }}}} }}}}")?;
+               }, TemplateAttrKey::P(p,_) => {
+                  write!(f, " {{{{ ..{} }}}}", rust_source(p))?;
+               }
+            }
+        }
+        if self.inner.crumbs.is_empty() && self.slots.is_empty() {
+           write!(f, "/>")
+        } else if is_raw_text(&self.tag) {
+           write!(f, ">")?;
+           for c in self.inner.crumbs.iter() {
+              match c {
+                 TemplateCrumb::S(s,_) => write!(f, "{}", s)?,
+                 c => write!(f, "{}", c)?,
+              }
+           }
+           write!(f, "</{}>", self.tag.source())
+        } else {
+           write!(f, ">")?;
+           for d in self.slots.iter() {
+              write!(f, "{}", d)?;
+           }
+           if gap(self.inner_span_start, self.inner.span()) { write!(f, " ")?; }
+           write!(f, "{}", self.inner)?;
+           if gap(self.inner.span(), self.inner_span_end) { write!(f, " ")?; }
+           write!(f, "</{}>", self.tag.source())
+        }
+    }
+}
+
+impl<D: Dialect> Parse for TemplateTag<D> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let tag = input.call(TemplateTag::parse_unchecked)?;
+        tag.validate()?;
+        Ok(tag)
+    }
+}
+
+impl<D: Dialect> TemplateTag<D> {
+    /// Parse a tag without [`TemplateTag::validate`], for a template that validates it as a whole.
+    pub fn parse_unchecked(input: ParseStream) -> Result<Self> {
+        let l1: Token![<] = input.parse()?;
+        let t: TemplateName = input.parse()?;
+
+        let mut attrs: Vec<(TemplateAttrKey,Option<TemplateAttr<D>>)> = Vec::new();
+        while peek_name(input) ||
+              input.peek(LitStr) ||
+              input.peek(Brace) {
+            if input.peek(Brace) {
+               let content1;
+               let content2;
+               let _brace1: Brace = braced!(content1 in input);
+               let brace2: Brace = braced!(content2 in content1);
+               if content2.peek(Token![..]) {
+                  let _dots: Token![..] = content2.parse()?;
+                  let expr: Expr = content2.parse()?;
+                  attrs.push(( TemplateAttrKey::P(expr,brace2.span), None ));
+                  continue;
+               }
+               let _if: Token![if] = content2.parse()?;
+               let expr: Expr = content2.call(parse_condition)?;
+
+               let content3;
+               let content4;
+               let _brace3: Brace = braced!(content3 in content2);
+               let _brace4: Brace = braced!(content4 in content3);
+               let key = if content4.peek(LitStr) { let s:LitStr = content4.parse()?; TemplateName::new(None, s.value(), s.span())
+                         } else { content4.parse()? };
+               let v = if content4.peek(Token![=]) {
+                  let _eq: Token![=] = content4.parse()?;
+                  let attr_expr: TemplateAttr<D> = TemplateAttr::parse(&content4, key.local.replace("-", "_"))?;
+                  Some(attr_expr)
+               } else { None };
+               attrs.push(( TemplateAttrKey::G(expr,key), v ));
+            } else {
+               let key = if input.peek(LitStr) { let s:LitStr = input.parse()?; TemplateName::new(None, s.value(), s.span())
+                         } else { input.parse()? };
+               let v = if input.peek(Token![=]) {
+                  let _eq: Token![=] = input.parse()?;
+                  let attr_expr: TemplateAttr<D> = TemplateAttr::parse(input, key.local.replace("-", "_"))?;
+                  Some(attr_expr)
+               } else { None };
+               attrs.push(( TemplateAttrKey::S(key), v ));
+           }
+        }
+
+        if is_component(&t) {
+           check_component(&t, &attrs)?;
+        }
+        let tag = if input.peek(Token![/]) {
+           let r1: Token![/] = input.parse()?;
+           let r2: Token![>] = input.parse()?;
+
+           TemplateTag {
+              tag: t.clone(),
+              attrs,
+              inner: Template::new(vec!()),
+              slots: Vec::new(),
+              close: TemplateClose::S,
+              outer_span: l1.span.join(r2.span).unwrap_or(l1.span),
+              inner_span_start: r1.span,
+              inner_span_end: r2.span,
+           }
+        } else {
+           let l2: Token![>] = input.parse()?;
+
+           let (inner, slots) = if is_raw_text(&t) {
+              (Template::new(parse_raw(input, &t, l2.span)?), Vec::new())
+           } else if is_component(&t) {
+              parse_children(input, &t, &attrs)?
+           } else {
+              (input.call(Template::parse_inner)?, Vec::new())
+           };
+           if input.is_empty() {
+              TemplateTag {
+                 tag: t.clone(),
+                 attrs,
+                 inner,
+                 slots,
+                 close: TemplateClose::U,
+                 outer_span: l1.span.join(l2.span).unwrap_or(l1.span),
+                 inner_span_start: l2.span,
+                 inner_span_end: l2.span,
+              }
+           } else {
+              let r1: Token![<] = input.parse()?;
+              let _r2: Token![/] = input.parse()?;
+              let t2: TemplateName = input.parse()?;
+              let r3: Token![>] = input.parse()?;
+
+              TemplateTag {
+                 tag: t.clone(),
+                 attrs,
+                 inner,
+                 slots,
+                 close: TemplateClose::C(t2),
+                 outer_span: l1.span.join(r3.span).unwrap_or(l1.span),
+                 inner_span_start: l2.span,
+                 inner_span_end: r1.span,
+              }
+           }
+        };
+
+        if !tag.is_component() {
+           check_merged(&tag)?;
+        }
+        Ok(tag)
+    }
+}
+
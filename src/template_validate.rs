@@ -0,0 +1,105 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use syn::parse::{Result, Error};
+use crate::template::{Template, TemplateCrumb, TemplateTag, TemplateAttrKey, TemplateClose, TemplateName};
+use crate::template::visit::{Visit, visit_tag};
+use crate::template_merged::{merged_into,declared_prefix,prefix_conflict};
+use crate::template_name::same_name;
+use crate::html::{element_kind,ElementKind};
+use crate::dialect::Dialect;
+
+/// Collects every structural error in a template instead of stopping at the first.
+struct Validate {
+   errors: Option<Error>,
+   // The merged attributes declared as namespace prefixes by the enclosing tags.
+   declared: Vec<&'static str>,
+}
+
+impl Validate {
+   fn push(&mut self, e: Error) {
+      match &mut self.errors {
+         Some(es) => es.combine(e),
+         None => { self.errors = Some(e); }
+      }
+   }
+   fn finish(self) -> Result<()> {
+      match self.errors {
+         Some(e) => Err(e),
+         None => Ok(())
+      }
+   }
+}
+
+impl<'ast, D: Dialect> Visit<'ast, D> for Validate {
+   fn visit_tag(&mut self, t: &'ast TemplateTag<D>) {
+      // HTML names are not case sensitive, but components and their fields are.
+      let component = t.is_component();
+      let same = |a: &TemplateName, b: &TemplateName| if component { a == b } else { same_name(a, b) };
+      let depth = self.declared.len();
+      for (k,_) in t.attrs.iter() {
+         if let TemplateAttrKey::S(k) | TemplateAttrKey::G(_,k) = k {
+            self.declared.extend(declared_prefix(k));
+         }
+      }
+      let mut seen: Vec<&TemplateName> = Vec::new();
+      for (k,_) in t.attrs.iter() {
+         if let TemplateAttrKey::S(k) | TemplateAttrKey::G(_,k) = k {
+            if k.prefix.is_some() && matches!(merged_into(k), Some(m) if self.declared.contains(&m)) {
+               self.push(prefix_conflict(k));
+            }
+         }
+         if let TemplateAttrKey::S(k) = k {
+            if merged_into(k).is_some() { continue; }
+            if seen.iter().any(|s| same(s, k)) {
+               let msg = format!("Attribute {} of <{}> is already given", k, t.tag);
+               self.push(Error::new(k.span, msg));
+            }
+            seen.push(k);
+         }
+      }
+      match &t.close {
+         TemplateClose::C(c) if !same(c, &t.tag) => {
+            let msg = format!("Expected </{}> found </{}>", t.tag, c);
+            self.push(Error::new(c.span, msg));
+         },
+         TemplateClose::U => {
+            let msg = format!("Unclosed tag <{}>", t.tag);
+            self.push(Error::new(t.tag.span, msg));
+         },
+         _ => {}
+      }
+      if !t.inner.crumbs.is_empty() && element_kind(&t.tag) == ElementKind::Void {
+         let msg = format!("Void element <{}> can not have children", t.tag);
+         self.push(Error::new(t.tag.span, msg));
+      }
+      if element_kind(&t.tag) == ElementKind::EscapableRawText {
+         for c in t.inner.crumbs.iter() {
+            if let TemplateCrumb::T(c) = c {
+               let msg = format!("Element <{}> can only contain text, not <{}>", t.tag, c.tag);
+               self.push(Error::new(c.tag.span, msg));
+            }
+         }
+      }
+      visit_tag(self, t);
+      self.declared.truncate(depth);
+   }
+}
+
+/// Report every unclosed or mismatched tag, duplicate attribute, void element
+/// with children, tag inside escapable raw text and `class:`/`style:` contribution
+/// under an `xmlns:class`/`xmlns:style` declaration in `t`, combined into one error.
+pub fn validate<D: Dialect>(t: &Template<D>) -> Result<()> {
+   let mut v = Validate { errors: None, declared: Vec::new() };
+   v.visit_template(t);
+   v.finish()
+}
+
+/// Report every structural error in the tag `t` and its children, as [`validate`] does.
+pub fn validate_tag<D: Dialect>(t: &TemplateTag<D>) -> Result<()> {
+   let mut v = Validate { errors: None, declared: Vec::new() };
+   v.visit_tag(t);
+   v.finish()
+}
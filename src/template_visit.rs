@@ -0,0 +1,496 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! Traversal of the template syntax tree in the style of `syn::visit`, `syn::visit_mut` and `syn::fold`.
+//!
+//! Each trait method has a default that walks the children of its node by
+//! calling the free function of the same name. An override can call that
+//! function to continue the walk.
+
+use proc_macro2::{Span};
+use syn::{Expr, Pat};
+use crate::template::{Template, TemplateCrumb, TemplateTag, TemplateAttrKey, TemplateAttr, TemplateExpr, TemplateExprInner,
+                 BracketedExpr, TemplateFragment, TemplateDoctype, TemplateComment, TemplateClass, TemplateClassAttr, TemplateClassChild, TemplateDisplayExpr, TemplateDisplay};
+use crate::dialect::Dialect;
+
+/// Read-only traversal of a template syntax tree.
+pub trait Visit<'ast, D: Dialect> {
+   fn visit_template(&mut self, i: &'ast Template<D>) { visit_template(self, i) }
+   fn visit_crumb(&mut self, i: &'ast TemplateCrumb<D>) { visit_crumb(self, i) }
+   fn visit_text(&mut self, _text: &'ast str, _span: Span) {}
+   fn visit_tag(&mut self, i: &'ast TemplateTag<D>) { visit_tag(self, i) }
+   fn visit_tag_attr(&mut self, k: &'ast TemplateAttrKey, v: &'ast Option<TemplateAttr<D>>) { visit_tag_attr(self, k, v) }
+   fn visit_attr(&mut self, i: &'ast TemplateAttr<D>) { visit_attr(self, i) }
+   fn visit_expr(&mut self, i: &'ast TemplateExpr<D>) { visit_expr(self, i) }
+   fn visit_expr_inner(&mut self, i: &'ast TemplateExprInner<D>) { visit_expr_inner(self, i) }
+   fn visit_bracketed_expr(&mut self, i: &'ast BracketedExpr) { visit_bracketed_expr(self, i) }
+   fn visit_fragment(&mut self, i: &'ast TemplateFragment<D>) { visit_fragment(self, i) }
+   fn visit_doctype(&mut self, _i: &'ast TemplateDoctype) {}
+   fn visit_comment(&mut self, _i: &'ast TemplateComment) {}
+   fn visit_stripped_comment(&mut self, _i: &'ast TemplateComment) {}
+   fn visit_class(&mut self, i: &'ast TemplateClass<D>) { visit_class(self, i) }
+   fn visit_class_attr(&mut self, k: &'ast str, v: &'ast TemplateClassAttr<D>) { visit_class_attr(self, k, v) }
+   fn visit_class_child(&mut self, i: &'ast TemplateClassChild<D>) { visit_class_child(self, i) }
+   fn visit_display_expr(&mut self, i: &'ast TemplateDisplayExpr<D>) { visit_display_expr(self, i) }
+   fn visit_rust_expr(&mut self, _i: &'ast Expr) {}
+   fn visit_rust_pat(&mut self, _i: &'ast Pat) {}
+}
+
+pub fn visit_template<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast Template<D>) {
+   for c in i.crumbs.iter() {
+      v.visit_crumb(c);
+   }
+}
+
+pub fn visit_crumb<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateCrumb<D>) {
+   match i {
+      TemplateCrumb::S(s,span) => v.visit_text(s, *span),
+      TemplateCrumb::T(t) => v.visit_tag(t),
+      TemplateCrumb::E(e) => v.visit_expr(e),
+      TemplateCrumb::F(f) => v.visit_bracketed_expr(f),
+      TemplateCrumb::C(c) => v.visit_class(c),
+      TemplateCrumb::D(d) => v.visit_doctype(d),
+      TemplateCrumb::M(m) => v.visit_comment(m),
+      TemplateCrumb::N(n) => v.visit_stripped_comment(n),
+      TemplateCrumb::G(g) => v.visit_fragment(g),
+   }
+}
+
+pub fn visit_fragment<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateFragment<D>) {
+   v.visit_template(&i.inner);
+}
+
+pub fn visit_tag<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateTag<D>) {
+   for (k,a) in i.attrs.iter() {
+      v.visit_tag_attr(k, a);
+   }
+   for d in i.slots.iter() {
+      v.visit_display_expr(d);
+   }
+   v.visit_template(&i.inner);
+}
+
+pub fn visit_tag_attr<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, k: &'ast TemplateAttrKey, a: &'ast Option<TemplateAttr<D>>) {
+   match k {
+      TemplateAttrKey::G(g,_) | TemplateAttrKey::P(g,_) => v.visit_rust_expr(g),
+      TemplateAttrKey::S(_) => {},
+   }
+   if let Some(a) = a {
+      v.visit_attr(a);
+   }
+}
+
+pub fn visit_attr<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateAttr<D>) {
+   match i {
+      TemplateAttr::S(_) => {},
+      TemplateAttr::F(f) => v.visit_bracketed_expr(f),
+      TemplateAttr::E(e) => v.visit_expr(e),
+      TemplateAttr::G(g) => v.visit_fragment(g),
+   }
+}
+
+pub fn visit_expr<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateExpr<D>) {
+   v.visit_expr_inner(&i.expr);
+}
+
+pub fn visit_expr_inner<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateExprInner<D>) {
+   match i {
+      TemplateExprInner::S(e) => v.visit_rust_expr(e),
+      TemplateExprInner::E(e) => v.visit_rust_expr(e),
+      TemplateExprInner::F(_,p,e,cs) => {
+         v.visit_rust_pat(p);
+         v.visit_rust_expr(e);
+         for c in cs.iter() { v.visit_crumb(c); }
+      },
+      TemplateExprInner::W(_,e,cs) => {
+         v.visit_rust_expr(e);
+         for c in cs.iter() { v.visit_crumb(c); }
+      },
+      TemplateExprInner::L(_,p,e) => {
+         v.visit_rust_pat(p);
+         v.visit_rust_expr(e);
+      },
+      TemplateExprInner::I(_,e,bs,es,x) => {
+         v.visit_rust_expr(e);
+         for c in bs.iter() { v.visit_crumb(c); }
+         for (e,cs) in es.iter() {
+            v.visit_rust_expr(e);
+            for c in cs.iter() { v.visit_crumb(c); }
+         }
+         for c in x.iter() { v.visit_crumb(c); }
+      },
+      TemplateExprInner::P(_,cs) => {
+         for c in cs.iter() { v.visit_crumb(c); }
+      },
+      TemplateExprInner::M(_,e,arms) => {
+         v.visit_rust_expr(e);
+         for (p,g,cs) in arms.iter() {
+            v.visit_rust_pat(p);
+            if let Some(g) = g { v.visit_rust_expr(g); }
+            for c in cs.iter() { v.visit_crumb(c); }
+         }
+      },
+   }
+}
+
+pub fn visit_bracketed_expr<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast BracketedExpr) {
+   v.visit_rust_expr(&i.expr);
+}
+
+pub fn visit_class<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateClass<D>) {
+   for (k,a) in i.attrs.iter() {
+      v.visit_class_attr(k, a);
+   }
+   for c in i.children.iter() {
+      v.visit_class_child(c);
+   }
+}
+
+pub fn visit_class_attr<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, _k: &'ast str, i: &'ast TemplateClassAttr<D>) {
+   match i {
+      TemplateClassAttr::Cl(c) => v.visit_class(c),
+      TemplateClassAttr::F(_,_,e) => v.visit_rust_expr(e),
+      TemplateClassAttr::E(_,e) => v.visit_rust_expr(e),
+      _ => {}
+   }
+}
+
+pub fn visit_class_child<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateClassChild<D>) {
+   match i {
+      TemplateClassChild::C(c) => v.visit_class(c),
+      TemplateClassChild::D(d) => v.visit_display_expr(d),
+   }
+}
+
+pub fn visit_display_expr<'ast, D: Dialect, V: Visit<'ast, D> + ?Sized>(v: &mut V, i: &'ast TemplateDisplayExpr<D>) {
+   match &i.expr {
+      TemplateDisplay::X(x) => v.visit_template(x),
+   }
+}
+
+/// Traversal of a template syntax tree that may modify it in place.
+pub trait VisitMut<D: Dialect> {
+   fn visit_template_mut(&mut self, i: &mut Template<D>) { visit_template_mut(self, i) }
+   fn visit_crumb_mut(&mut self, i: &mut TemplateCrumb<D>) { visit_crumb_mut(self, i) }
+   fn visit_text_mut(&mut self, _text: &mut String, _span: &mut Span) {}
+   fn visit_tag_mut(&mut self, i: &mut TemplateTag<D>) { visit_tag_mut(self, i) }
+   fn visit_tag_attr_mut(&mut self, k: &mut TemplateAttrKey, v: &mut Option<TemplateAttr<D>>) { visit_tag_attr_mut(self, k, v) }
+   fn visit_attr_mut(&mut self, i: &mut TemplateAttr<D>) { visit_attr_mut(self, i) }
+   fn visit_expr_mut(&mut self, i: &mut TemplateExpr<D>) { visit_expr_mut(self, i) }
+   fn visit_expr_inner_mut(&mut self, i: &mut TemplateExprInner<D>) { visit_expr_inner_mut(self, i) }
+   fn visit_bracketed_expr_mut(&mut self, i: &mut BracketedExpr) { visit_bracketed_expr_mut(self, i) }
+   fn visit_fragment_mut(&mut self, i: &mut TemplateFragment<D>) { visit_fragment_mut(self, i) }
+   fn visit_doctype_mut(&mut self, _i: &mut TemplateDoctype) {}
+   fn visit_comment_mut(&mut self, _i: &mut TemplateComment) {}
+   fn visit_stripped_comment_mut(&mut self, _i: &mut TemplateComment) {}
+   fn visit_class_mut(&mut self, i: &mut TemplateClass<D>) { visit_class_mut(self, i) }
+   fn visit_class_attr_mut(&mut self, k: &mut String, v: &mut TemplateClassAttr<D>) { visit_class_attr_mut(self, k, v) }
+   fn visit_class_child_mut(&mut self, i: &mut TemplateClassChild<D>) { visit_class_child_mut(self, i) }
+   fn visit_display_expr_mut(&mut self, i: &mut TemplateDisplayExpr<D>) { visit_display_expr_mut(self, i) }
+   fn visit_rust_expr_mut(&mut self, _i: &mut Expr) {}
+   fn visit_rust_pat_mut(&mut self, _i: &mut Pat) {}
+}
+
+pub fn visit_template_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut Template<D>) {
+   for c in i.crumbs.iter_mut() {
+      v.visit_crumb_mut(c);
+   }
+}
+
+pub fn visit_crumb_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateCrumb<D>) {
+   match i {
+      TemplateCrumb::S(s,span) => v.visit_text_mut(s, span),
+      TemplateCrumb::T(t) => v.visit_tag_mut(t),
+      TemplateCrumb::E(e) => v.visit_expr_mut(e),
+      TemplateCrumb::F(f) => v.visit_bracketed_expr_mut(f),
+      TemplateCrumb::C(c) => v.visit_class_mut(c),
+      TemplateCrumb::D(d) => v.visit_doctype_mut(d),
+      TemplateCrumb::M(m) => v.visit_comment_mut(m),
+      TemplateCrumb::N(n) => v.visit_stripped_comment_mut(n),
+      TemplateCrumb::G(g) => v.visit_fragment_mut(g),
+   }
+}
+
+pub fn visit_fragment_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateFragment<D>) {
+   v.visit_template_mut(&mut i.inner);
+}
+
+pub fn visit_tag_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateTag<D>) {
+   for (k,a) in i.attrs.iter_mut() {
+      v.visit_tag_attr_mut(k, a);
+   }
+   for d in i.slots.iter_mut() {
+      v.visit_display_expr_mut(d);
+   }
+   v.visit_template_mut(&mut i.inner);
+}
+
+pub fn visit_tag_attr_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, k: &mut TemplateAttrKey, a: &mut Option<TemplateAttr<D>>) {
+   match k {
+      TemplateAttrKey::G(g,_) | TemplateAttrKey::P(g,_) => v.visit_rust_expr_mut(g),
+      TemplateAttrKey::S(_) => {},
+   }
+   if let Some(a) = a {
+      v.visit_attr_mut(a);
+   }
+}
+
+pub fn visit_attr_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateAttr<D>) {
+   match i {
+      TemplateAttr::S(_) => {},
+      TemplateAttr::F(f) => v.visit_bracketed_expr_mut(f),
+      TemplateAttr::E(e) => v.visit_expr_mut(e),
+      TemplateAttr::G(g) => v.visit_fragment_mut(g),
+   }
+}
+
+pub fn visit_expr_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateExpr<D>) {
+   v.visit_expr_inner_mut(&mut i.expr);
+}
+
+pub fn visit_expr_inner_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateExprInner<D>) {
+   match i {
+      TemplateExprInner::S(e) => v.visit_rust_expr_mut(e),
+      TemplateExprInner::E(e) => v.visit_rust_expr_mut(e),
+      TemplateExprInner::F(_,p,e,cs) => {
+         v.visit_rust_pat_mut(p);
+         v.visit_rust_expr_mut(e);
+         for c in cs.iter_mut() { v.visit_crumb_mut(c); }
+      },
+      TemplateExprInner::W(_,e,cs) => {
+         v.visit_rust_expr_mut(e);
+         for c in cs.iter_mut() { v.visit_crumb_mut(c); }
+      },
+      TemplateExprInner::L(_,p,e) => {
+         v.visit_rust_pat_mut(p);
+         v.visit_rust_expr_mut(e);
+      },
+      TemplateExprInner::I(_,e,bs,es,x) => {
+         v.visit_rust_expr_mut(e);
+         for c in bs.iter_mut() { v.visit_crumb_mut(c); }
+         for (e,cs) in es.iter_mut() {
+            v.visit_rust_expr_mut(e);
+            for c in cs.iter_mut() { v.visit_crumb_mut(c); }
+         }
+         for c in x.iter_mut() { v.visit_crumb_mut(c); }
+      },
+      TemplateExprInner::P(_,cs) => {
+         for c in cs.iter_mut() { v.visit_crumb_mut(c); }
+      },
+      TemplateExprInner::M(_,e,arms) => {
+         v.visit_rust_expr_mut(e);
+         for (p,g,cs) in arms.iter_mut() {
+            v.visit_rust_pat_mut(p);
+            if let Some(g) = g { v.visit_rust_expr_mut(g); }
+            for c in cs.iter_mut() { v.visit_crumb_mut(c); }
+         }
+      },
+   }
+}
+
+pub fn visit_bracketed_expr_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut BracketedExpr) {
+   v.visit_rust_expr_mut(&mut i.expr);
+}
+
+pub fn visit_class_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateClass<D>) {
+   for (k,a) in i.attrs.iter_mut() {
+      v.visit_class_attr_mut(k, a);
+   }
+   for c in i.children.iter_mut() {
+      v.visit_class_child_mut(c);
+   }
+}
+
+pub fn visit_class_attr_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, _k: &mut String, i: &mut TemplateClassAttr<D>) {
+   match i {
+      TemplateClassAttr::Cl(c) => v.visit_class_mut(c),
+      TemplateClassAttr::F(_,_,e) => v.visit_rust_expr_mut(e),
+      TemplateClassAttr::E(_,e) => v.visit_rust_expr_mut(e),
+      _ => {}
+   }
+}
+
+pub fn visit_class_child_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateClassChild<D>) {
+   match i {
+      TemplateClassChild::C(c) => v.visit_class_mut(c),
+      TemplateClassChild::D(d) => v.visit_display_expr_mut(d),
+   }
+}
+
+pub fn visit_display_expr_mut<D: Dialect, V: VisitMut<D> + ?Sized>(v: &mut V, i: &mut TemplateDisplayExpr<D>) {
+   match &mut i.expr {
+      TemplateDisplay::X(x) => v.visit_template_mut(x),
+   }
+}
+
+/// Traversal of a template syntax tree that builds a new tree from the old one.
+pub trait Fold<D: Dialect> {
+   fn fold_template(&mut self, i: Template<D>) -> Template<D> { fold_template(self, i) }
+   fn fold_crumb(&mut self, i: TemplateCrumb<D>) -> TemplateCrumb<D> { fold_crumb(self, i) }
+   fn fold_text(&mut self, text: String, _span: Span) -> String { text }
+   fn fold_tag(&mut self, i: TemplateTag<D>) -> TemplateTag<D> { fold_tag(self, i) }
+   fn fold_tag_attr(&mut self, k: TemplateAttrKey, v: Option<TemplateAttr<D>>) -> (TemplateAttrKey, Option<TemplateAttr<D>>) { fold_tag_attr(self, k, v) }
+   fn fold_attr(&mut self, i: TemplateAttr<D>) -> TemplateAttr<D> { fold_attr(self, i) }
+   fn fold_expr(&mut self, i: TemplateExpr<D>) -> TemplateExpr<D> { fold_expr(self, i) }
+   fn fold_expr_inner(&mut self, i: TemplateExprInner<D>) -> TemplateExprInner<D> { fold_expr_inner(self, i) }
+   fn fold_bracketed_expr(&mut self, i: BracketedExpr) -> BracketedExpr { fold_bracketed_expr(self, i) }
+   fn fold_fragment(&mut self, i: TemplateFragment<D>) -> TemplateFragment<D> { fold_fragment(self, i) }
+   fn fold_doctype(&mut self, i: TemplateDoctype) -> TemplateDoctype { i }
+   fn fold_comment(&mut self, i: TemplateComment) -> TemplateComment { i }
+   fn fold_stripped_comment(&mut self, i: TemplateComment) -> TemplateComment { i }
+   fn fold_class(&mut self, i: TemplateClass<D>) -> TemplateClass<D> { fold_class(self, i) }
+   fn fold_class_attr(&mut self, k: String, v: TemplateClassAttr<D>) -> (String, TemplateClassAttr<D>) { fold_class_attr(self, k, v) }
+   fn fold_class_child(&mut self, i: TemplateClassChild<D>) -> TemplateClassChild<D> { fold_class_child(self, i) }
+   fn fold_display_expr(&mut self, i: TemplateDisplayExpr<D>) -> TemplateDisplayExpr<D> { fold_display_expr(self, i) }
+   fn fold_rust_expr(&mut self, i: Expr) -> Expr { i }
+   fn fold_rust_pat(&mut self, i: Pat) -> Pat { i }
+}
+
+fn fold_crumbs<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, cs: Vec<TemplateCrumb<D>>) -> Vec<TemplateCrumb<D>> {
+   cs.into_iter().map(|c| f.fold_crumb(c)).collect()
+}
+
+pub fn fold_template<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: Template<D>) -> Template<D> {
+   Template::new(fold_crumbs(f, i.crumbs))
+}
+
+pub fn fold_crumb<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateCrumb<D>) -> TemplateCrumb<D> {
+   match i {
+      TemplateCrumb::S(s,span) => TemplateCrumb::S(f.fold_text(s, span), span),
+      TemplateCrumb::T(t) => TemplateCrumb::T(f.fold_tag(t)),
+      TemplateCrumb::E(e) => TemplateCrumb::E(f.fold_expr(e)),
+      TemplateCrumb::F(b) => TemplateCrumb::F(f.fold_bracketed_expr(b)),
+      TemplateCrumb::C(c) => TemplateCrumb::C(f.fold_class(c)),
+      TemplateCrumb::D(d) => TemplateCrumb::D(f.fold_doctype(d)),
+      TemplateCrumb::M(m) => TemplateCrumb::M(f.fold_comment(m)),
+      TemplateCrumb::N(n) => TemplateCrumb::N(f.fold_stripped_comment(n)),
+      TemplateCrumb::G(g) => TemplateCrumb::G(f.fold_fragment(g)),
+   }
+}
+
+pub fn fold_fragment<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateFragment<D>) -> TemplateFragment<D> {
+   TemplateFragment {
+      inner: f.fold_template(i.inner),
+      ..i
+   }
+}
+
+pub fn fold_tag<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateTag<D>) -> TemplateTag<D> {
+   TemplateTag {
+      attrs: i.attrs.into_iter().map(|(k,a)| f.fold_tag_attr(k, a)).collect(),
+      slots: i.slots.into_iter().map(|d| f.fold_display_expr(d)).collect(),
+      inner: f.fold_template(i.inner),
+      ..i
+   }
+}
+
+pub fn fold_tag_attr<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, k: TemplateAttrKey, a: Option<TemplateAttr<D>>) -> (TemplateAttrKey, Option<TemplateAttr<D>>) {
+   let k = match k {
+      TemplateAttrKey::G(g,k) => TemplateAttrKey::G(f.fold_rust_expr(g), k),
+      TemplateAttrKey::P(p,span) => TemplateAttrKey::P(f.fold_rust_expr(p), span),
+      k => k
+   };
+   (k, a.map(|a| f.fold_attr(a)))
+}
+
+pub fn fold_attr<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateAttr<D>) -> TemplateAttr<D> {
+   match i {
+      TemplateAttr::S(s) => TemplateAttr::S(s),
+      TemplateAttr::F(b) => TemplateAttr::F(f.fold_bracketed_expr(b)),
+      TemplateAttr::E(e) => TemplateAttr::E(f.fold_expr(e)),
+      TemplateAttr::G(g) => TemplateAttr::G(f.fold_fragment(g)),
+   }
+}
+
+pub fn fold_expr<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateExpr<D>) -> TemplateExpr<D> {
+   TemplateExpr {
+      expr: f.fold_expr_inner(i.expr),
+      ..i
+   }
+}
+
+pub fn fold_expr_inner<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateExprInner<D>) -> TemplateExprInner<D> {
+   match i {
+      TemplateExprInner::S(e) => TemplateExprInner::S(f.fold_rust_expr(e)),
+      TemplateExprInner::E(e) => TemplateExprInner::E(f.fold_rust_expr(e)),
+      TemplateExprInner::F(t,p,e,cs) => {
+         let p = f.fold_rust_pat(p);
+         let e = f.fold_rust_expr(e);
+         TemplateExprInner::F(t, p, e, fold_crumbs(f, cs))
+      },
+      TemplateExprInner::W(t,e,cs) => {
+         let e = f.fold_rust_expr(e);
+         TemplateExprInner::W(t, e, fold_crumbs(f, cs))
+      },
+      TemplateExprInner::L(t,p,e) => {
+         let p = f.fold_rust_pat(p);
+         TemplateExprInner::L(t, p, f.fold_rust_expr(e))
+      },
+      TemplateExprInner::I(t,e,bs,es,x) => {
+         let e = f.fold_rust_expr(e);
+         let bs = fold_crumbs(f, bs);
+         let es = es.into_iter().map(|(e,cs)| {
+            let e = f.fold_rust_expr(e);
+            (e, fold_crumbs(f, cs))
+         }).collect();
+         TemplateExprInner::I(t, e, bs, es, fold_crumbs(f, x))
+      },
+      TemplateExprInner::P(t,cs) => {
+         TemplateExprInner::P(t, fold_crumbs(f, cs))
+      },
+      TemplateExprInner::M(t,e,arms) => {
+         let e = f.fold_rust_expr(e);
+         let arms = arms.into_iter().map(|(p,g,cs)| {
+            let p = f.fold_rust_pat(p);
+            let g = g.map(|g| f.fold_rust_expr(g));
+            (p, g, fold_crumbs(f, cs))
+         }).collect();
+         TemplateExprInner::M(t, e, arms)
+      },
+   }
+}
+
+pub fn fold_bracketed_expr<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: BracketedExpr) -> BracketedExpr {
+   BracketedExpr {
+      expr: f.fold_rust_expr(i.expr),
+      ..i
+   }
+}
+
+pub fn fold_class<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateClass<D>) -> TemplateClass<D> {
+   TemplateClass {
+      attrs: i.attrs.into_iter().map(|(k,a)| f.fold_class_attr(k, a)).collect(),
+      children: i.children.into_iter().map(|c| f.fold_class_child(c)).collect(),
+      ..i
+   }
+}
+
+pub fn fold_class_attr<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, k: String, i: TemplateClassAttr<D>) -> (String, TemplateClassAttr<D>) {
+   let i = match i {
+      TemplateClassAttr::Cl(c) => TemplateClassAttr::Cl(f.fold_class(c)),
+      TemplateClassAttr::F(b,s,e) => TemplateClassAttr::F(b, s, f.fold_rust_expr(e)),
+      TemplateClassAttr::E(b,e) => TemplateClassAttr::E(b, f.fold_rust_expr(e)),
+      i => i
+   };
+   (k, i)
+}
+
+pub fn fold_class_child<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateClassChild<D>) -> TemplateClassChild<D> {
+   match i {
+      TemplateClassChild::C(c) => TemplateClassChild::C(f.fold_class(c)),
+      TemplateClassChild::D(d) => TemplateClassChild::D(f.fold_display_expr(d)),
+   }
+}
+
+pub fn fold_display_expr<D: Dialect, F: Fold<D> + ?Sized>(f: &mut F, i: TemplateDisplayExpr<D>) -> TemplateDisplayExpr<D> {
+   let expr = match i.expr {
+      TemplateDisplay::X(x) => TemplateDisplay::X(f.fold_template(x)),
+   };
+   TemplateDisplayExpr {
+      expr,
+      ..i
+   }
+}
@@ -3,10 +3,11 @@
 // see the LICENSE file or <http://opensource.org/licenses/MIT>
 // also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
 
-use quote::{quote_spanned, ToTokens};
-use proc_macro2::{Span, Literal};
+use quote::{ToTokens};
+use proc_macro2::{Span};
 use syn::parse::{Parse, ParseStream, Result};
 use syn::{Ident, Token, LitChar, LitBool, LitStr, LitInt};
+use crate::emit::Emitter;
 
 pub struct TokenAsLiteral {
    pub token_literal: String,
@@ -239,10 +240,9 @@ impl Parse for TokenAsLiteral {
 
 impl ToTokens for TokenAsLiteral {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let l = Literal::string(&self.token_literal);
-        (quote_spanned!{self.span.clone()=>
-           stream.push_str(#l);
-        }).to_tokens(tokens);
+        let mut e = Emitter::default();
+        e.push_str(&self.token_literal, self.span);
+        e.finish().to_tokens(tokens);
     }
 }
 
@@ -0,0 +1,103 @@
+
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+use std::fmt;
+use quote::ToTokens;
+use proc_macro2::{Span, Literal};
+
+/// Keywords that `TokenAsLiteral` does not accept as bare words.
+const RESERVED: [&str; 5] = ["_", "async", "await", "dyn", "try"];
+
+/// Punctuation that `TokenAsLiteral` accepts as a bare text crumb.
+const PUNCT: &str = "!#@$%^*-+=|:;,.?&/~";
+
+fn is_word(s: &str) -> bool {
+   let mut cs = s.chars();
+   match cs.next() {
+      Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+         cs.all(|c| c.is_ascii_alphanumeric() || c == '_') && !RESERVED.contains(&s)
+      },
+      _ => false
+   }
+}
+
+fn is_number(s: &str) -> bool {
+   !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) && (s == "0" || !s.starts_with('0'))
+}
+
+/// Source text for a text crumb: a bare word, number or punctuation when that
+/// reparses to the same text, otherwise a string literal.
+pub fn text_source(s: &str) -> String {
+   if is_word(s) || is_number(s) || (s.len() == 1 && PUNCT.contains(s)) {
+      s.to_string()
+   } else {
+      Literal::string(s).to_string()
+   }
+}
+
+/// Source text for one part of a tag or attribute name, which may be `ident(-ident)*`.
+pub fn name_source(s: &str) -> String {
+   let part = |p: &str| p != "_" && (is_word(p) || RESERVED.contains(&p));
+   if s.split('-').all(part) {
+      s.to_string()
+   } else {
+      Literal::string(s).to_string()
+   }
+}
+
+/// Source text for a static xhtml attribute value, which is stored already
+/// quoted and escaped.
+pub fn attr_value_source(s: &str) -> String {
+   if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+      let v = s[1..s.len()-1]
+         .replace("&quot;", "\"")
+         .replace("&#39;", "'")
+         .replace("&lt;", "<")
+         .replace("&gt;", ">")
+         .replace("&amp;", "&");
+      Literal::string(&v).to_string()
+   } else {
+      s.to_string()
+   }
+}
+
+/// Rust source of an expression, pattern or other syntax tree.
+pub fn rust_source<T: ToTokens>(t: &T) -> String {
+   t.to_token_stream().to_string()
+}
+
+/// Whether the source had whitespace between `a` and `b`.
+pub fn gap(a: Span, b: Span) -> bool {
+   b.start() > a.end()
+}
+
+/// Whether `a` and `b` would lex as different tokens if written without a space.
+pub fn needs_space(a: &str, b: &str) -> bool {
+   let (l, r) = match (a.chars().last(), b.chars().next()) {
+      (Some(l), Some(r)) => (l, r),
+      _ => return false
+   };
+   let word = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '"' || c == '\'';
+   (word(l) && word(r)) ||
+   (l == '/' && (r == '/' || r == '*')) ||
+   (l.is_ascii_digit() && r == '.')
+}
+
+/// Write the source of a sequence of crumbs, separating them where the
+/// original had whitespace or where the tokens would otherwise run together.
+pub fn write_spaced(f: &mut fmt::Formatter, pieces: &[(Span, String)]) -> fmt::Result {
+   let mut prev: Option<&(Span, String)> = None;
+   for p in pieces.iter() {
+      if let Some(q) = prev {
+         if gap(q.0, p.0) || needs_space(&q.1, &p.1) {
+            write!(f, " ")?;
+         }
+      }
+      write!(f, "{}", p.1)?;
+      prev = Some(p);
+   }
+   Ok(())
+}
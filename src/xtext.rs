@@ -4,53 +4,30 @@
 // see the LICENSE file or <http://opensource.org/licenses/MIT>
 // also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
 
-use quote::{quote_spanned, ToTokens};
-use proc_macro2::{Span};
-use syn::parse::{Parse, ParseStream, Result};
+//! The `xtext!` dialect of `template`.
 
-pub use crate::bracketed_expr::BracketedExpr;
-pub use crate::xtext_display_expr::XtextDisplayExpr;
-pub use crate::xtext_expr::XtextExpr;
-pub use crate::xtext_class_attr::XtextClassAttr;
-pub use crate::xtext_attr::XtextAttr;
-pub use crate::xtext_class_child::XtextClassChild;
-pub use crate::xtext_class::XtextClass;
-pub use crate::xtext_tag::XtextTag;
-pub use crate::xtext_crumb::XtextCrumb;
+use crate::template::*;
+pub use crate::template::BracketedExpr;
+pub use crate::dialect::XtextDialect;
 
-pub struct Xtext {
-    pub crumbs: Vec<XtextCrumb>
+pub mod visit {
+    pub use crate::template_visit::*;
 }
 
-impl Xtext {
-    pub fn span(&self) -> Span {
-       if self.crumbs.len() > 0 {
-          let mut span = self.crumbs[0].span();
-          for c in self.crumbs[1..].iter() {
-             span = span.join(c.span()).unwrap_or(c.span());
-          }
-          span
-       } else {
-          Span::call_site()
-       }
-    }
-}
-
-impl ToTokens for Xtext {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let mut prev: Option<Span> = None;
-        for c in self.crumbs.iter() {
-            c.to_tokens(tokens);
-        }
-    }
-}
-
-impl Parse for Xtext {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let crumbs: Vec<XtextCrumb> = input.call(XtextCrumb::parse_outer)?;
-
-        Ok(Xtext {
-            crumbs: crumbs
-        })
-    }
-}
+pub type Xtext = Template<XtextDialect>;
+pub type XtextCrumb = TemplateCrumb<XtextDialect>;
+pub type XtextTag = TemplateTag<XtextDialect>;
+pub type XtextAttrKey = TemplateAttrKey;
+pub type XtextClose = TemplateClose;
+pub type XtextName = TemplateName;
+pub type XtextDoctype = TemplateDoctype;
+pub type XtextComment = TemplateComment;
+pub type XtextAttr = TemplateAttr<XtextDialect>;
+pub type XtextExpr = TemplateExpr<XtextDialect>;
+pub type XtextExprInner = TemplateExprInner<XtextDialect>;
+pub type XtextFragment = TemplateFragment<XtextDialect>;
+pub type XtextClass = TemplateClass<XtextDialect>;
+pub type XtextClassAttr = TemplateClassAttr<XtextDialect>;
+pub type XtextClassChild = TemplateClassChild<XtextDialect>;
+pub type XtextDisplayExpr = TemplateDisplayExpr<XtextDialect>;
+pub type XtextDisplay = TemplateDisplay<XtextDialect>;
@@ -0,0 +1,55 @@
+// Copyright 2020, The rdxl Project Developers.
+// Dual Licensed under the MIT license and the Apache 2.0 license,
+// see the LICENSE file or <http://opensource.org/licenses/MIT>
+// also see LICENSE2 file or <https://www.apache.org/licenses/LICENSE-2.0>
+
+//! `xhtml!` and `xtext!` macros that evaluate to a `String`, so that the
+//! tests of rdxl_internals can compile and run the code it generates.
+
+extern crate proc_macro;
+
+use quote::quote;
+use syn::parse_macro_input;
+use rdxl_internals::dialect::Output;
+use rdxl_internals::xhtml::Xhtml;
+use rdxl_internals::xtext::Xtext;
+
+fn expand(body: proc_macro2::TokenStream) -> proc_macro::TokenStream {
+   (quote! {
+      {
+         let mut stream = ::std::string::String::new();
+         #body
+         stream
+      }
+   }).into()
+}
+
+fn expand_with(t: &Xhtml, output: Output) -> proc_macro::TokenStream {
+   let mut body = proc_macro2::TokenStream::new();
+   t.to_tokens_with(&quote! { stream }, output, &mut body);
+   expand(body)
+}
+
+#[proc_macro]
+pub fn xhtml(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+   let t = parse_macro_input!(input as Xhtml);
+   expand(quote! { #t })
+}
+
+#[proc_macro]
+pub fn xhtml_html5(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+   let t = parse_macro_input!(input as Xhtml);
+   expand_with(&t, Output::Html5)
+}
+
+#[proc_macro]
+pub fn xhtml_xml(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+   let t = parse_macro_input!(input as Xhtml);
+   expand_with(&t, Output::Xml)
+}
+
+#[proc_macro]
+pub fn xtext(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+   let t = parse_macro_input!(input as Xtext);
+   expand(quote! { #t })
+}
@@ -0,0 +1,27 @@
+use rdxl_internals::class_derive::derive_class;
+use syn::parse_quote;
+
+#[test]
+fn derive1() {
+   let item: syn::ItemStruct = parse_quote! {
+      pub struct Card {
+         title: String,
+         #[children(Item, ui::Header)]
+         children: Vec<CardChildren>,
+      }
+   };
+   let ts = derive_class(&item).unwrap().to_string();
+   assert!(ts.contains("pub enum CardChildren { Display (Box < dyn :: std :: fmt :: Display >) , Item (Item) , Header (ui :: Header) , }"));
+   assert!(ts.contains("CardChildren :: Header (c) => :: std :: fmt :: Display :: fmt (c , f) ,"));
+   assert!(ts.contains("impl :: std :: default :: Default for Card"));
+   assert!(ts.contains("title : :: std :: default :: Default :: default () ,"));
+}
+
+#[test]
+fn derive2() {
+   let item: syn::ItemStruct = parse_quote! {
+      struct Card { title: String }
+   };
+   let e = derive_class(&item).unwrap_err();
+   assert_eq!(e.to_string(), "Class Card needs a field children: Vec<CardChildren>");
+}
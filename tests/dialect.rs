@@ -0,0 +1,38 @@
+use rdxl_internals::dialect::{Dialect, Escape, Output};
+use rdxl_internals::template::Template;
+use rdxl_internals::xtext::Xtext;
+use quote::ToTokens;
+
+struct Compact;
+impl Dialect for Compact {
+   const WHITESPACE: bool = false;
+   const ATTRIBUTE: Escape = Escape::Attribute;
+   const RENDER: bool = true;
+   const OUTPUT: Output = Output::Html5;
+   fn quote_attribute(value: &str, _is_char: bool) -> String {
+      format!("\"{}\"", value)
+   }
+   fn attribute_source(stored: &str) -> String {
+      stored.to_string()
+   }
+}
+
+#[test]
+fn dialect1() {
+   let x: Template<Compact> = syn::parse_str(r#"<p a="b"> hello world </p>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some(r#"<p a="b">helloworld</p>"#.to_string()) );
+}
+
+#[test]
+fn dialect2() {
+   let x: Template<Compact> = syn::parse_str(r#"<p>{{ x }}</p>"#).unwrap();
+   assert!( x.to_token_stream().to_string().contains("Render :: render") );
+   let x: Xtext = syn::parse_str(r#"<p>{{ x }}</p>"#).unwrap();
+   assert!( x.to_token_stream().to_string().contains("push_display") );
+}
+
+#[test]
+fn dialect3() {
+   let x: Template<Compact> = syn::parse_str(r#"<p>a<br/>b<img src="x"/></p>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some(r#"<p>a<br>b<img src="x"></p>"#.to_string()) );
+}
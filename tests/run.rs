@@ -0,0 +1,124 @@
+#![deny(warnings)]
+
+use rdxl_internals::runtime::{Component, Sink};
+use rdxl_test_macros::{xhtml, xhtml_html5, xhtml_xml, xtext};
+
+#[test]
+fn run1() {
+   let n = "3 < 4";
+   let s = xhtml!(<p class="a">{{ n }}</p>);
+   assert_eq!( s, "<p class=\"a\">3 &lt; 4</p>" );
+}
+
+#[test]
+fn run2() {
+   let (flag, title, none) = (true, "a\"b", None::<u32>);
+   let s = xhtml!(<input disabled={{ flag }} title={{ title }} size={{ none }}/>);
+   assert_eq!( s, "<input disabled=\"disabled\" title=\"a&quot;b\"/>" );
+}
+
+#[test]
+fn run3() {
+   let attrs = vec![("href", "y"), ("title", "s"), ("id", "i")];
+   let s = xhtml!(<a href="x" {{ if true {{ title="t" }} }} {{..attrs.clone()}}>b</a>);
+   assert_eq!( s, "<a href=\"x\" title=\"t\" id=\"i\">b</a>" );
+   let s = xhtml!(<a href="x" {{ if false {{ title="t" }} }} {{..attrs.clone()}}>b</a>);
+   assert_eq!( s, "<a href=\"x\" title=\"s\" id=\"i\">b</a>" );
+}
+
+#[test]
+fn run4() {
+   let s = xtext!(<p>{{ "a<b" }}</p>);
+   assert_eq!( s, "<p>a<b</p>" );
+}
+
+#[test]
+fn run5() {
+   let (flag, title, text) = (true, "a'b", "<c>");
+   let s = xhtml_xml!(<input disabled checked={{ flag }} title={{ title }}/>);
+   assert_eq!( s, "<input disabled=\"disabled\" checked=\"checked\" title=\"a&#39;b\"/>" );
+   assert_eq!( xhtml_xml!(<b>{{ text }}</b>), "<b>&#60;c&#62;</b>" );
+   assert_eq!( xhtml_xml!(<i n=3 on=true></i>), "<i n=\"3\" on=\"true\"/>" );
+   assert_eq!( xhtml_html5!(<input disabled checked={{ flag }}/>), "<input disabled checked>" );
+   assert_eq!( xhtml_html5!(<b>{{ text }}</b>), "<b>&lt;c&gt;</b>" );
+}
+
+#[test]
+fn run6() {
+   // Each block body ends with a space in xhtml.
+   for &(n, out) in &[(1, "<b>one</b> "), (3, "<i>3</i> "), (7, "many ")] {
+      let s = xhtml!({{ match n { 1 => {{ <b>one</b> }}, 2 | 3 => {{ <i>{{ n }}</i> }}, _ => {{ "many" }} } }});
+      assert_eq!( s, out );
+   }
+}
+
+#[test]
+fn run7() {
+   let (some, none) = (Some("a<b"), None::<&str>);
+   assert_eq!( xhtml!({{ if let Some(v) = some {{ <b>{{ v }}</b> }} else {{ <i>none</i> }} }}), "<b>a&lt;b</b> " );
+   assert_eq!( xhtml!({{ if let Some(v) = none {{ <b>{{ v }}</b> }} else {{ <i>none</i> }} }}), "<i>none</i> " );
+   let mut xs = vec![1, 2];
+   assert_eq!( xhtml!({{ while let Some(x) = xs.pop() {{ <i>{{ x }}</i> }} }}), "<i>2</i> <i>1</i> " );
+}
+
+#[test]
+fn run8() {
+   let xs = ["a", "b"];
+   assert_eq!( xhtml!(<>{{ "a" }}</>), "a" );
+   assert_eq!( xhtml!({{ for x in xs.iter() {{ <><b>{{ x }}</b></> }} }}), "<b>a</b> <b>b</b> " );
+}
+
+#[derive(Default)]
+struct Card {
+   title: String,
+   count: u32,
+   big: bool,
+   header: String,
+}
+
+impl Component for Card {
+   fn render(&self, stream: &mut dyn Sink, children: &mut dyn FnMut(&mut dyn Sink)) {
+      stream.push_str("<div title=\"");
+      stream.push_text(&self.title);
+      stream.push_str(&format!("\" data-count=\"{}\" data-big=\"{}\">", self.count, self.big));
+      stream.push_str(&self.header);
+      children(stream);
+      stream.push_str("</div>");
+   }
+}
+
+#[test]
+fn run9() {
+   let n = 2;
+   let s = xhtml!(<Card title="a&b" count={{ n }} big><b>{{ "<x>" }}</b></Card>);
+   assert_eq!( s, "<div title=\"a&amp;b\" data-count=\"2\" data-big=\"true\"><b>&lt;x&gt;</b></div>" );
+   assert_eq!( xhtml!(<Card/>), "<div title=\"\" data-count=\"0\" data-big=\"false\"></div>" );
+}
+
+#[test]
+fn run10() {
+   let s = xhtml!(<Card><?header><h1>h</h1></?></Card>);
+   assert_eq!( s, "<div title=\"\" data-count=\"0\" data-big=\"false\"><h1>h</h1></div>" );
+}
+
+#[test]
+fn run11() {
+   let (on, color) = (true, "red");
+   let s = xhtml!(<a class="btn" class:active={{ on }} class:off={{ !on }} style:color={{ color }}></a>);
+   assert_eq!( s, "<a class=\"btn active\" style=\"color:red\"></a>" );
+}
+
+#[test]
+fn run12() {
+   let v = "1; alert(1)</script>";
+   let s = xhtml!(<script>var x = {{ v }};</script>);
+   assert_eq!( s, "<script>var x = \"1; alert(1)\\u003c/script\\u003e\";</script>" );
+}
+
+#[test]
+fn run13() {
+   let s = xhtml_html5!(<Card><?header><input disabled/></?></Card>);
+   assert_eq!( s, "<div title=\"\" data-count=\"0\" data-big=\"false\"><input disabled></div>" );
+   let s = xhtml_xml!(<Card><?header><p></p></?></Card>);
+   assert_eq!( s, "<div title=\"\" data-count=\"0\" data-big=\"false\"><p/></div>" );
+}
@@ -0,0 +1,143 @@
+use rdxl_internals::runtime::{escape_html, escape_attribute, escape_xml, XmlSink, push_display, Render, PreEscaped, Sink, AttributeSink, FmtSink, IoSink, ScriptStringSink, escape_script_string, spread_attributes, AttributeValue, DisplayAttributeValue, AttributeList};
+
+#[test]
+fn escape1() {
+   assert_eq!( escape_html("<a href=\"x\">'&'</a>"), "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;" );
+}
+
+#[test]
+fn escape2() {
+   assert_eq!( escape_html("plain text ü"), "plain text ü" );
+}
+
+#[test]
+fn render1() {
+   let mut stream = String::new();
+   "<script>".render(&mut stream);
+   3.render(&mut stream);
+   assert_eq!( stream, "&lt;script&gt;3" );
+}
+
+#[test]
+fn render2() {
+   let mut stream = String::new();
+   PreEscaped("<b>bold</b>").render(&mut stream);
+   assert_eq!( stream, "<b>bold</b>" );
+}
+
+#[test]
+fn attribute1() {
+   let mut stream = String::new();
+   {
+      let mut stream = AttributeSink::new(&mut stream);
+      stream.push_str("\"><script>");
+      "it's".render(&mut stream);
+   }
+   assert_eq!( stream, "&quot;&gt;&lt;script&gt;it&#39;s" );
+}
+
+#[test]
+fn xml1() {
+   assert_eq!( escape_xml("<a href=\"x\">it's & more</a>"), "&#60;a href=&#34;x&#34;&#62;it&#39;s &#38; more&#60;/a&#62;" );
+   let mut stream = String::new();
+   {
+      let mut stream = XmlSink::new(&mut stream);
+      stream.push_str("<b>");
+      "a<b".render(&mut stream);
+      "\"".render(&mut AttributeSink::new(&mut stream));
+   }
+   assert_eq!( stream, "<b>a&#60;b&#34;" );
+}
+
+#[test]
+fn attribute2() {
+   assert_eq!( escape_attribute("a\"b&c"), "a&quot;b&amp;c" );
+}
+
+#[test]
+fn fmt_sink1() {
+   struct Page(&'static str);
+   impl std::fmt::Display for Page {
+      fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+         let mut stream = FmtSink::new(f);
+         stream.push_str("<p>");
+         self.0.render(&mut stream);
+         stream.push_str("</p>");
+         stream.finish()
+      }
+   }
+   assert_eq!( Page("a<b").to_string(), "<p>a&lt;b</p>" );
+}
+
+#[test]
+fn io_sink1() {
+   let mut stream = IoSink::new(Vec::new());
+   push_display(&mut stream, &"<br>");
+   "&".render(&mut stream);
+   assert_eq!( stream.into_inner(), b"<br>&amp;" );
+}
+
+#[test]
+fn script1() {
+   assert_eq!( escape_script_string("</script><!-- \"a\" & 'b'\\\n\u{2028}"), r#"\u003c/script\u003e\u003c!-- \"a\" \u0026 \u0027b\u0027\\\n\u2028"# );
+   let mut s = String::new();
+   "1; alert(1)".render(&mut ScriptStringSink::new(&mut s));
+   assert_eq!( s, "1; alert(1)" );
+}
+
+struct Split(&'static [&'static str]);
+
+impl std::fmt::Display for Split {
+   fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+      for p in self.0.iter() {
+         f.write_str(p)?;
+      }
+      Ok(())
+   }
+}
+
+#[test]
+fn script2() {
+   let mut s = String::new();
+   Split(&["<", "/script><b>x</b>", "<!", "-", "-x", " a<"]).render(&mut ScriptStringSink::new(&mut s));
+   assert_eq!( s, r#"\u003c/script\u003e\u003cb\u003ex\u003c/b\u003e\u003c!--x a\u003c"# );
+}
+
+#[test]
+fn spread1() {
+   let attrs = vec![("id","a"), ("class","x"), ("bad key","y"), ("id","b"), ("title","t")];
+   assert_eq!( spread_attributes(attrs, &["class"]), vec![("id","b"), ("title","t")] );
+}
+
+#[test]
+fn attribute_value1() {
+   let mut s = String::new();
+   true.push_attribute("disabled", true, &mut s);
+   false.push_attribute("hidden", true, &mut s);
+   None::<&str>.push_attribute("title", true, &mut s);
+   Some("a\"b").push_attribute("alt", true, &mut s);
+   (&3).push_attribute("tabindex", true, &mut s);
+   (&"x<y").push_attribute("value", true, &mut s);
+   assert_eq!( s, " disabled alt=\"a&quot;b\" tabindex=\"3\" value=\"x&lt;y\"" );
+}
+
+#[test]
+fn attribute_list1() {
+   let mut c = AttributeList::classes();
+   c.push("btn ");
+   c.push("");
+   c.push(&3);
+   assert_eq!( c.as_str(), "btn 3" );
+   let mut s = AttributeList::styles();
+   s.push("margin:0;");
+   s.push_property("color", "");
+   s.push_property("width", &"2px");
+   assert_eq!( s.to_string(), "margin:0;width:2px" );
+}
+
+#[test]
+fn attribute_value2() {
+   let mut s = String::new();
+   true.push_attribute("disabled", false, &mut s);
+   assert_eq!( s, " disabled=\"disabled\"" );
+}
@@ -0,0 +1,51 @@
+use rdxl_internals::xhtml::Xhtml;
+use rdxl_internals::schema::{Schema, ClassSchema};
+use quote::quote;
+
+fn schema() -> Schema {
+   Schema::new()
+      .class("Card", ClassSchema::new().attr("color").attr("title").slot("header").child("Item"))
+}
+
+fn parse_err(ts: proc_macro2::TokenStream) -> String {
+   match schema().scope(|| syn::parse2::<Xhtml>(ts)) {
+      Ok(_) => panic!("expected an error"),
+      Err(e) => e.to_string()
+   }
+}
+
+#[test]
+fn schema1() {
+   let ts = quote! { <!Card color="red"><?header>a</?><!Item/></Card> };
+   assert!(schema().scope(|| syn::parse2::<Xhtml>(ts)).is_ok());
+   let ts = quote! { <!Other colr="red"/> };
+   assert!(schema().scope(|| syn::parse2::<Xhtml>(ts)).is_ok());
+}
+
+#[test]
+fn schema2() {
+   assert_eq!(parse_err(quote! { <!Card colr="red"/> }), "<!Card> has no attribute colr, did you mean color?");
+   assert_eq!(parse_err(quote! { <!Card size="2"/> }), "<!Card> has no attribute size");
+   assert_eq!(parse_err(quote! { <!Card header="x"/> }), "header of <!Card> is a slot, write it as <?header>…</?>");
+}
+
+#[test]
+fn schema3() {
+   assert_eq!(parse_err(quote! { <!Card><?heder>a</?></Card> }), "<!Card> has no slot heder, did you mean header?");
+   assert_eq!(parse_err(quote! { <!Card><?title>a</?></Card> }), "title of <!Card> is an attribute, not a slot");
+   assert_eq!(parse_err(quote! { <!Card><!Iten/></Card> }), "<!Card> can not contain <!Iten>, did you mean Item?");
+}
+
+#[test]
+fn schema4() {
+   let ts = quote! { <!Card colr="red"/> };
+   assert!(syn::parse2::<Xhtml>(ts).is_ok());
+}
+
+#[test]
+fn schema5() {
+   let r = std::panic::catch_unwind(|| schema().scope(|| panic!("in scope")));
+   assert!(r.is_err());
+   let ts = quote! { <!Card colr="red"/> };
+   assert!(syn::parse2::<Xhtml>(ts).is_ok());
+}
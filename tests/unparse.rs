@@ -0,0 +1,53 @@
+use rdxl_internals::xhtml::Xhtml;
+use rdxl_internals::xtext::Xtext;
+use quote::ToTokens;
+
+fn round_trip(src: &str) -> String {
+   let x: Xhtml = syn::parse_str(src).unwrap();
+   let printed = x.to_string();
+   let y: Xhtml = syn::parse_str(&printed).unwrap();
+   assert_eq!( printed, y.to_string() );
+   assert_eq!( x.to_token_stream().to_string(), y.to_token_stream().to_string() );
+   printed
+}
+
+#[test]
+fn unparse1() {
+   assert_eq!( round_trip(r#"<div class="a">hello "big" "wide world"</div>"#), r#"<div class="a">hello big "wide world"</div>"# );
+}
+
+#[test]
+fn unparse2() {
+   assert_eq!( round_trip(r#"<p> a, b; "x > y" </p><br/>"#), r#"<p> a, b; "x > y" </p><br/>"# );
+}
+
+#[test]
+fn unparse3() {
+   let printed = round_trip(r#"<ul>{{ for x in xs {{ <li>{{ x }}</li> }} }}</ul>"#);
+   assert_eq!( printed, r#"<ul>{{ for x in xs {{ <li>{{ x }}</li> }} }}</ul>"# );
+}
+
+#[test]
+fn unparse4() {
+   let printed = round_trip(r#"{{ if let Some(x) = y {{ a }} else if z {{ b }} else {{ c }} }} {{ let n = 3; }} {{ match n { 1 | 2 if ok => {{ few }}, _ => {{ many }} } }}"#);
+   assert!( printed.contains("{{ let n = 3; }}") );
+   assert!( printed.contains("{{ match n { 1 | 2 if ok => {{ few }}, _ => {{ many }}, } }}") );
+}
+
+#[test]
+fn unparse5() {
+   let printed = round_trip(r#"<a href=[[ url ]] title={{ t }} {{ if on {{ data-on=true }} }} alt="q&quot;">x</a>"#);
+   assert_eq!( printed, r#"<a href=[[ url ]] title={{ t }} {{ if on {{ data-on=true }} }} alt="q&quot;">x</a>"# );
+}
+
+#[test]
+fn unparse6() {
+   let printed = round_trip(r#"<!Card title="t" n=3 on=true c='x' sub=<!Icon/> v={{ a }} w=[[ b ]]><?><b>x</b></?><!Item/></Card>"#);
+   assert_eq!( printed, r#"<!Card title="t" n=3 on=true c='x' sub=<!Icon/> v={{ a }} w=[[ b ]]><?><b>x</b></?><!Item/></Card>"# );
+}
+
+#[test]
+fn unparse7() {
+   let x: Xtext = syn::parse_str(r#""Hello, " [[ name ]] {{ if x {{ "!" }} }}"#).unwrap();
+   assert_eq!( x.to_string(), r#""Hello, " [[ name ]] {{ if x {{ ! }} }}"# );
+}
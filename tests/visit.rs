@@ -0,0 +1,79 @@
+use rdxl_internals::xhtml::{Xhtml, XhtmlTag, XhtmlDialect};
+use rdxl_internals::xhtml::visit::{self, Visit, VisitMut, Fold};
+use rdxl_internals::xtext::{Xtext, XtextDialect};
+use proc_macro2::Span;
+use quote::ToTokens;
+
+#[derive(Default)]
+struct Names {
+   tags: Vec<String>,
+   exprs: Vec<String>,
+}
+impl<'ast> Visit<'ast, XhtmlDialect> for Names {
+   fn visit_tag(&mut self, i: &'ast XhtmlTag) {
+      self.tags.push(i.tag.to_string());
+      visit::visit_tag(self, i);
+   }
+   fn visit_rust_expr(&mut self, i: &'ast syn::Expr) {
+      self.exprs.push(i.to_token_stream().to_string());
+   }
+}
+
+#[test]
+fn visit1() {
+   let x: Xhtml = syn::parse_str(r#"<ul class={{c}}>{{ for x in xs {{ <li>{{ x }}</li> }} }}</ul>"#).unwrap();
+   let mut v = Names::default();
+   v.visit_template(&x);
+   assert_eq!( v.tags, vec!["ul", "li"] );
+   assert_eq!( v.exprs, vec!["c", "xs", "x"] );
+}
+
+#[test]
+fn visit2() {
+   let x: Xhtml = syn::parse_str(r#"{{ match m { Some(y) if y > 1 => {{ <b>{{ y }}</b> }} _ => {{ }} } }}"#).unwrap();
+   let mut v = Names::default();
+   v.visit_template(&x);
+   assert_eq!( v.tags, vec!["b"] );
+   assert_eq!( v.exprs, vec!["m", "y > 1", "y"] );
+}
+
+#[test]
+fn visit3() {
+   struct Count(usize);
+   impl<'ast> Visit<'ast, XtextDialect> for Count {
+      fn visit_text(&mut self, _text: &'ast str, _span: Span) {
+         self.0 += 1;
+      }
+   }
+   let x: Xtext = syn::parse_str(r#"a <b>c</b> {{ if t {{ d }} }}"#).unwrap();
+   let mut v = Count(0);
+   v.visit_template(&x);
+   assert_eq!( v.0, 3 );
+}
+
+#[test]
+fn visit_mut1() {
+   struct Upper;
+   impl VisitMut<XhtmlDialect> for Upper {
+      fn visit_text_mut(&mut self, text: &mut String, _span: &mut Span) {
+         *text = text.to_uppercase();
+      }
+   }
+   let mut x: Xhtml = syn::parse_str(r#"<p>hello {{ if t {{ world }} }}</p>"#).unwrap();
+   Upper.visit_template_mut(&mut x);
+   assert_eq!( x.to_string(), r#"<p>HELLO {{ if t {{ WORLD }} }}</p>"# );
+}
+
+#[test]
+fn fold1() {
+   struct Rename;
+   impl Fold<XhtmlDialect> for Rename {
+      fn fold_tag(&mut self, i: XhtmlTag) -> XhtmlTag {
+         let i = visit::fold_tag(self, i);
+         if i.tag == "b" { XhtmlTag { tag: "strong".into(), ..i } } else { i }
+      }
+   }
+   let x: Xhtml = syn::parse_str(r#"<p><b>a</b> {{ for x in xs {{ <b>{{ x }}</b> }} }}</p>"#).unwrap();
+   let x = Rename.fold_template(x);
+   assert_eq!( x.to_string(), r#"<p><strong>a</strong> {{ for x in xs {{ <strong>{{ x }}</strong> }} }}</p>"# );
+}
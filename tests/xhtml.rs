@@ -1,4 +1,6 @@
 use rdxl_internals::xhtml::Xhtml;
+use rdxl_internals::dialect::Output;
+use quote::ToTokens;
 use syn::parse_quote;
 
 #[test]
@@ -10,3 +12,216 @@ fn token_literal1() {
 fn token_literal2() {
    let _: Xhtml = parse_quote! { <div>{{ "abc" }}</div> };
 }
+
+#[test]
+fn writer1() {
+   let x: Xhtml = parse_quote! { <div>{{ "abc" }}</div> };
+   let writer: syn::Expr = parse_quote! { sink };
+   let mut ts = proc_macro2::TokenStream::new();
+   x.to_tokens_into(&writer, &mut ts);
+   let ts = ts.to_string();
+   assert!( !ts.contains("stream") );
+   assert!( ts.contains("Sink :: push_str (& mut (sink) , \"<div>\")") );
+   assert!( ts.contains("Render :: render (& (\"abc\") , & mut (sink))") );
+}
+
+#[test]
+fn coalesce1() {
+   let x: Xhtml = syn::parse_str(r#"<div class="a">hello world</div>"#).unwrap();
+   let ts = x.to_token_stream().to_string();
+   assert_eq!( ts.matches("push_str").count(), 1 );
+   assert!( ts.contains(r#""<div class=\"a\">hello world</div>""#) );
+   assert_eq!( x.capacity_hint(), 32 );
+}
+
+#[test]
+fn coalesce2() {
+   let x: Xhtml = syn::parse_str(r#"<p>a {{ b }} c</p>"#).unwrap();
+   let ts = x.to_token_stream().to_string();
+   assert_eq!( ts.matches("push_str").count(), 2 );
+   assert!( ts.contains(r#""<p>a ""#) );
+   assert!( ts.contains(r#"" c</p>""#) );
+}
+
+#[test]
+fn static1() {
+   let x: Xhtml = syn::parse_str(r#"<footer><a href="/about">About</a></footer>"#).unwrap();
+   assert_eq!( x.as_static_str().unwrap(), r#"<footer><a href="/about">About</a></footer>"# );
+   let ts = x.to_static_tokens().unwrap().to_string();
+   assert_eq!( ts, r#""<footer><a href=\"/about\">About</a></footer>""# );
+}
+
+#[test]
+fn static2() {
+   let x: Xhtml = parse_quote! { <p>{{ name }}</p> };
+   assert!( x.as_static_str().is_none() );
+   let x: Xhtml = parse_quote! { <p {{ if x {{ hidden }} }}></p> };
+   assert!( x.to_static_tokens().is_none() );
+}
+
+#[test]
+fn doctype1() {
+   let x: Xhtml = syn::parse_str(r#"<!DOCTYPE html><html><body>hi</body></html>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some("<!DOCTYPE html><html><body>hi</body></html>".to_string()) );
+   let x: Xhtml = syn::parse_str(r#"<!doctype html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN">"#).unwrap();
+   assert_eq!( x.as_static_str(), Some(r#"<!doctype html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN">"#.to_string()) );
+}
+
+#[test]
+fn comment1() {
+   let x: Xhtml = syn::parse_str(r#"<p>a <!-- note "this" --> b</p>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some("<p>a <!-- note this --> b</p>".to_string()) );
+   assert_eq!( x.to_string(), r#"<p>a <!-- note this --> b</p>"# );
+}
+
+#[test]
+fn comment2() {
+   let x: Xhtml = syn::parse_str(r#"<p>a<!--- only in the template --->b</p>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some("<p>ab</p>".to_string()) );
+   assert_eq!( x.to_string(), r#"<p>a<!--- only in the template --->b</p>"# );
+}
+
+#[test]
+fn comment3() {
+   let r: syn::Result<Xhtml> = syn::parse_str(r#"<!-- a "--" b -->"#);
+   assert!( r.is_err() );
+   let x: Xhtml = syn::parse_str(r#"<!Page title="x"/>"#).unwrap();
+   assert!( x.to_token_stream().to_string().contains("Page") );
+}
+
+#[test]
+fn fragment1() {
+   let x: Xhtml = syn::parse_str(r#"<ul><><li>a</li> <li>b</li></></ul>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some("<ul><li>a</li> <li>b</li></ul>".to_string()) );
+   assert_eq!( x.to_string(), r#"<ul><><li>a</li> <li>b</li></></ul>"# );
+}
+
+#[test]
+fn fragment2() {
+   let x: Xhtml = syn::parse_str(r#"<a title=<>x {{ y }}</>>z</a>"#).unwrap();
+   let ts = x.to_token_stream().to_string();
+   assert!( ts.contains(r#""<a title=\"x ""#) );
+   assert!( ts.contains("AttributeSink :: new (& mut (stream))") );
+   let r: syn::Result<Xhtml> = syn::parse_str(r#"<>a</b>"#);
+   assert_eq!( r.err().unwrap().to_string(), "Expected </> found </b>" );
+}
+
+#[test]
+fn fragment3() {
+   let x: Xhtml = syn::parse_str(r#"<!Page><?><><b>a</b> c</></?></Page>"#).unwrap();
+   let ts = x.to_token_stream().to_string();
+   assert!( ts.contains(r#"String :: from ("<b>a</b> c")"#) );
+}
+
+#[test]
+fn slot1() {
+   let x: Xhtml = syn::parse_str(r#"<!Layout title="t"><?header><h1>a</h1></?><?>body</?><?footer>f</?footer></Layout>"#).unwrap();
+   let ts = x.to_token_stream().to_string();
+   assert!( ts.contains(r#"header : String :: from ("<h1>a</h1>") ,"#) );
+   assert!( ts.contains(r#"footer : String :: from ("f") ,"#) );
+   assert!( ts.contains(r#"children : vec ! [LayoutChildren :: Display (Box :: new (String :: from ("body"))) ,]"#) );
+   assert_eq!( x.to_string(), r#"<!Layout title="t"><?header><h1>a</h1></?><?>body</?><?footer>f</?></Layout>"# );
+}
+
+#[test]
+fn slot2() {
+   let r: syn::Result<Xhtml> = syn::parse_str(r#"<!Layout><?header>a</?><?header>b</?></Layout>"#);
+   assert_eq!( r.err().unwrap().to_string(), "Slot header of <!Layout> is already given" );
+   let r: syn::Result<Xhtml> = syn::parse_str(r#"<!Layout><?header>a</?footer></Layout>"#);
+   assert_eq!( r.err().unwrap().to_string(), "Expected </?header> found </?footer>" );
+}
+
+fn errors(s: &str) -> Vec<String> {
+   match syn::parse_str::<Xhtml>(s) {
+      Ok(_) => Vec::new(),
+      Err(e) => e.into_iter().map(|e| e.to_string()).collect()
+   }
+}
+
+#[test]
+fn validate1() {
+   assert_eq!( errors(r#"<a href="x" href="y">a</a><br>b</br><p id="1" class="a" class="b" id="2"/>"#), vec![
+      "Attribute href of <a> is already given",
+      "Void element <br> can not have children",
+      "Attribute id of <p> is already given",
+   ]);
+}
+
+#[test]
+fn validate2() {
+   assert_eq!( errors("a</b> c</>"), vec!["Stray closing tag </b>", "Stray closing tag </>"] );
+   assert_eq!( errors("{{ for x in xs {{ </i> }} }}"), vec!["Stray closing tag </i>"] );
+   assert_eq!( errors("<div><p>a"), vec!["Unclosed tag <div>", "Unclosed tag <p>"] );
+   assert_eq!( errors("<>a"), vec!["Unclosed fragment <>"] );
+}
+
+#[test]
+fn validate3() {
+   assert_eq!( errors(r#"<a></b><i id="1" ID="2">c</i><p>d</q>"#), vec![
+      "Expected </a> found </b>",
+      "Attribute ID of <i> is already given",
+      "Expected </p> found </q>",
+   ]);
+   assert_eq!( errors(r#"<Card title="a" Title="b"></Card>"#), Vec::<String>::new() );
+}
+
+#[test]
+fn validate4() {
+   assert_eq!( errors(r#"<P>a</p><DIV id="1"><span>b</SPAN></div><SCRIPT>var a;</script>"#), Vec::<String>::new() );
+   assert_eq!( errors(r#"<i id="1" Id="2"></I><Card></card><svg:a></SVG:a>"#), vec![
+      "Attribute Id of <i> is already given",
+      "Expected </Card> found </card>",
+      "Expected </svg:a> found </SVG:a>",
+   ]);
+   assert_eq!( errors(r#"<svg xmlns:class="urn:c"><g class:x/></svg><g class:x/>"#), vec![
+      "class:x is merged into class, but the prefix is also a namespace; write a namespaced attribute as \"class:x\"",
+   ]);
+}
+
+#[test]
+fn output1() {
+   let x: Xhtml = syn::parse_str(r#"<p><input disabled/><br></br><div></div></p>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some(r#"<p><input disabled="disabled"/><br/><div></div></p>"#.to_string()) );
+   assert_eq!( x.as_static_str_with(Output::Html5), Some(r#"<p><input disabled><br><div></div></p>"#.to_string()) );
+   assert_eq!( x.as_static_str_with(Output::Xml), Some(r#"<p><input disabled="disabled"/><br/><div/></p>"#.to_string()) );
+}
+
+#[test]
+fn output2() {
+   let x: Xhtml = parse_quote! { <input checked={{ c }}/> };
+   let writer: syn::Expr = parse_quote! { stream };
+   let mut ts = proc_macro2::TokenStream::new();
+   x.to_tokens_with(&writer, Output::Html5, &mut ts);
+   assert!( ts.to_string().contains("push_attribute (\"checked\" , true , & mut (stream))") );
+}
+
+#[test]
+fn output3() {
+   let x: Xhtml = syn::parse_str(r#"<p n=3 on=true title="a<b" c='"'>x</p>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some(r#"<p n="3" on="true" title="a&lt;b" c="&quot;">x</p>"#.to_string()) );
+   assert_eq!( x.as_static_str_with(Output::Html5), Some(r#"<p n="3" on="true" title="a&lt;b" c="&quot;">x</p>"#.to_string()) );
+   assert_eq!( x.as_static_str_with(Output::Xml), Some(r#"<p n="3" on="true" title="a&#60;b" c="&#34;">x</p>"#.to_string()) );
+}
+
+#[test]
+fn output4() {
+   // Without a chosen output, xhtml writes boolean attributes in full.
+   let x: Xhtml = syn::parse_str(r#"<input disabled/>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some(r#"<input disabled="disabled"/>"#.to_string()) );
+   assert_eq!( x.as_static_str(), x.as_static_str_with(Output::Xhtml) );
+}
+
+#[test]
+fn output5() {
+   let writer: syn::Expr = parse_quote! { stream };
+   let x: Xhtml = syn::parse_str(r#"<!Layout><?header><br/></?><?><input disabled/></?></Layout>"#).unwrap();
+   let mut ts = proc_macro2::TokenStream::new();
+   x.to_tokens_with(&writer, Output::Html5, &mut ts);
+   let ts = ts.to_string();
+   assert!( ts.contains(r#"header : String :: from ("<br>")"#) );
+   assert!( ts.contains(r#"String :: from ("<input disabled>")"#) );
+   let x: Xhtml = syn::parse_str(r#"<Card><?header><p></p></?></Card>"#).unwrap();
+   let mut ts = proc_macro2::TokenStream::new();
+   x.to_tokens_with(&writer, Output::Xml, &mut ts);
+   assert!( ts.to_string().contains(r#"header : String :: from ("<p/>")"#) );
+}
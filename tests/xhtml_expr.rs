@@ -0,0 +1,53 @@
+use rdxl_internals::xhtml::XhtmlExpr;
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[test]
+fn expr1() {
+   let e: XhtmlExpr = parse_quote! { {{ user_input }} };
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.contains("Render :: render (& (user_input) , & mut (stream))") );
+}
+
+#[test]
+fn expr2() {
+   let e: XhtmlExpr = parse_quote! { {{ let x = 1; }} };
+   assert!( !e.does_emit() );
+}
+
+#[test]
+fn match1() {
+   let e: XhtmlExpr = parse_quote! { {{ match shape {
+      Shape::Circle(r) if r > 1.0 => {{ "big circle" }},
+      Shape::Circle(_) | Shape::Dot => {{ "small" }}
+      _ => {{ <b>{{ shape.name() }}</b> }}
+   } }} };
+   assert!( e.does_emit() );
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.starts_with("match shape {") );
+   assert!( ts.contains("Shape :: Circle (r) if r > 1.0 =>") );
+   assert!( ts.contains("Shape :: Circle (_) | Shape :: Dot =>") );
+}
+
+#[test]
+fn if_let1() {
+   let e: XhtmlExpr = parse_quote! { {{ if let Some(x) = opt {{ {{ x }} }} else if let Ok(y) | Err(y) = res {{ {{ y }} }} else if done {{ "done" }} }} };
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.starts_with("if let Some (x) = opt {") );
+   assert!( ts.contains("else if let Ok (y) | Err (y) = res {") );
+   assert!( ts.contains("else if done {") );
+}
+
+#[test]
+fn while_let1() {
+   let e: XhtmlExpr = parse_quote! { {{ while let Some(x) = it.next() {{ <li>{{ x }}</li> }} }} };
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.starts_with("while let Some (x) = it . next () {") );
+}
+
+#[test]
+fn for1() {
+   let e: XhtmlExpr = parse_quote! { {{ for x in xs {{ {{ x }} }} }} };
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.starts_with("for x in xs {") );
+}
@@ -1,4 +1,5 @@
 use rdxl_internals::xhtml::XhtmlTag;
+use quote::ToTokens;
 use syn::parse_quote;
 
 #[test]
@@ -22,3 +23,258 @@ fn tag2() {
    )
 }
 */
+
+#[test]
+fn attr1() {
+   let t: XhtmlTag = parse_quote! { <a title="say \"hi\" & go"></a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<a title=\"say &quot;hi&quot; &amp; go\"></a>""#) );
+}
+
+#[test]
+fn attr2() {
+   let t: XhtmlTag = parse_quote! { <a href={{ url }} title=[[ t ]]></a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("(& (url)) . push_attribute (\"href\" , false , & mut (stream))") );
+   assert!( ts.contains("(& (t . to_title ())) . push_attribute (\"title\" , false , & mut (stream))") );
+   assert!( !ts.contains("replace") );
+}
+
+#[test]
+fn attr3() {
+   let t: XhtmlTag = parse_quote! { <a {{ if c.is_some() {{ title={{ t }} }} }}></a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("if c . is_some ()") );
+   assert!( ts.contains("(& (t)) . push_attribute (\"title\" , false , & mut (stream))") );
+}
+
+#[test]
+fn attr4() {
+   let t: XhtmlTag = parse_quote! { <a {{ if let Some(t) = title {{ title={{ t }} }} }}></a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("if let Some (t) = title {") );
+}
+
+#[test]
+fn name1() {
+   let t: XhtmlTag = parse_quote! { <my-widget data-id="3" aria-label={{ l }} http-equiv="refresh" for="x"></my-widget> };
+   assert_eq!( t.tag, "my-widget" );
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<my-widget data-id=\"3\"""#) );
+   assert!( ts.contains(r#"(& (l)) . push_attribute ("aria-label" , false , & mut (stream))"#) );
+   assert!( ts.contains(r#"" http-equiv=\"refresh\" for=\"x\"></my-widget>""#) );
+}
+
+#[test]
+fn name2() {
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<my-widget></my-gadget>");
+   assert_eq!( r.err().unwrap().to_string(), "Expected </my-widget> found </my-gadget>" );
+}
+
+#[test]
+fn name3() {
+   let t: XhtmlTag = syn::parse_str(r#"<my-widget {{ if on {{ data-on }} }}>x</my-widget>"#).unwrap();
+   assert_eq!( t.to_string(), r#"<my-widget {{ if on {{ data-on }} }}>x</my-widget>"# );
+}
+
+#[test]
+fn namespace1() {
+   let t: XhtmlTag = parse_quote! { <svg:use xlink:href="a" {{ if on {{ xml:lang="en" }} }}></svg:use> };
+   assert_eq!( t.tag.prefix.as_deref(), Some("svg") );
+   assert_eq!( t.tag.local, "use" );
+   assert_eq!( t.tag, "svg:use" );
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<svg:use xlink:href=\"a\""#) );
+   assert!( ts.contains(r#"" xml:lang=\"en\"""#) );
+   assert!( ts.contains(r#""></svg:use>""#) );
+}
+
+#[test]
+fn namespace2() {
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<atom:link></atom:feed>");
+   assert_eq!( r.err().unwrap().to_string(), "Expected </atom:link> found </atom:feed>" );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<atom:link></link>");
+   assert_eq!( r.err().unwrap().to_string(), "Expected </atom:link> found </link>" );
+}
+
+#[test]
+fn namespace3() {
+   let t: XhtmlTag = syn::parse_str(r#"<dc:creator xml:lang="en">x</dc:creator>"#).unwrap();
+   assert_eq!( t.to_string(), r#"<dc:creator xml:lang="en">x</dc:creator>"# );
+}
+
+#[test]
+fn raw1() {
+   let t: XhtmlTag = syn::parse_str("<style>.a { color: red } #id > p::first-line { margin: 0 10px }</style>").unwrap();
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<style>.a { color: red } #id > p::first-line { margin: 0 10px }</style>""#) );
+}
+
+#[test]
+fn raw2() {
+   let src = "<script>\n  if (a < b) {\n    f(\"x\");\n  }\n</script>";
+   let t: XhtmlTag = syn::parse_str(src).unwrap();
+   assert_eq!( t.to_string(), src );
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<script>\n  if (a < b) {\n    f(\"x\");\n  }\n</script>""#) );
+}
+
+#[test]
+fn raw3() {
+   let t: XhtmlTag = syn::parse_str("<script>var x = {{ v }}; var y = { a: 1 };</script>").unwrap();
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<script>var x = \"""#) );
+   assert!( ts.contains("ScriptStringSink :: new (& mut (stream))") );
+   assert!( ts.contains(r#""\"; var y = { a: 1 };</script>""#) );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<script>{{ for x in xs {{ x }} }}</script>");
+   assert!( r.is_err() );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<style>.a { color: {{ c }} }</style>");
+   assert!( r.is_err() );
+}
+
+#[test]
+fn raw4() {
+   let t: XhtmlTag = parse_quote! { <script>var a = b - -c; if (x < -1) { a >>= 1; }</script> };
+   assert_eq!( t.to_string(), "<script>var a=b- -c;if(x< -1){a>>=1;}</script>" );
+}
+
+#[test]
+fn component1() {
+   let t: XhtmlTag = parse_quote! { <Card title="hi" count={{ n }} big><b>{{ body }}</b></Card> };
+   assert!( t.is_component() );
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.starts_with(":: rdxl_internals :: runtime :: Component :: render (& Card { title : :: std :: convert :: Into :: into (\"hi\") , count : (n) , big : true , .. :: std :: default :: Default :: default () } , & mut (stream) ,") );
+   assert!( ts.contains("& mut | stream : & mut dyn :: rdxl_internals :: runtime :: Sink | { :: rdxl_internals :: runtime :: Sink :: push_str (& mut (* stream) , \"<b>\") ;") );
+}
+
+#[test]
+fn component2() {
+   let t: XhtmlTag = parse_quote! { <HTML></HTML> };
+   assert!( !t.is_component() );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card {{ if c {{ open }} }}></Card>");
+   assert!( r.is_err() );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card title={{ for x in xs {{ x }} }}></Card>");
+   assert!( r.is_err() );
+}
+
+#[test]
+fn component3() {
+   let t: XhtmlTag = parse_quote! { <Card title="hi"><?header><h1>a</h1></?><b>b</b></Card> };
+   assert_eq!( t.slots.len(), 1 );
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("title : :: std :: convert :: Into :: into (\"hi\") , header : String :: from (\"<h1>a</h1>\") , .. :: std :: default :: Default :: default ()") );
+   assert!( ts.contains("\"<b>b</b>\"") );
+   assert!( !ts.contains("<h1>a</h1><b>") );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card><?>a</?></Card>");
+   assert!( r.is_err() );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card title=\"a\"><?title>b</?></Card>");
+   assert!( r.is_err() );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card><?header>a</?><?header>b</?></Card>");
+   assert!( r.is_err() );
+}
+
+#[test]
+fn component4() {
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card sub-title=\"a\" sub_title=\"b\"></Card>");
+   assert_eq!( r.err().unwrap().to_string(), "Field sub_title of <Card> is already given" );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card sub-title=\"a\"><?sub_title>b</?></Card>");
+   assert_eq!( r.err().unwrap().to_string(), "Slot sub_title of <Card> is already given" );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card x:header=\"a\"><?header>b</?></Card>");
+   assert_eq!( r.err().unwrap().to_string(), "Component <Card> can not have a namespaced attribute x:header" );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card {{..attrs}}><?header>b</?></Card>");
+   assert_eq!( r.err().unwrap().to_string(), "Component <Card> can not have a spread of attributes" );
+}
+
+#[test]
+fn spread1() {
+   let t: XhtmlTag = parse_quote! { <a href="x" {{..attrs}}>b</a> };
+   assert_eq!( t.to_string(), "<a href=\"x\" {{ ..attrs }}>b</a>" );
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("for (attr_key , attr_value) in :: rdxl_internals :: runtime :: spread_attributes (attrs , & [\"href\"])") );
+   assert!( ts.contains(":: rdxl_internals :: runtime :: Render :: render (& (attr_value) , & mut :: rdxl_internals :: runtime :: AttributeSink :: new (& mut (stream)))") );
+}
+
+#[test]
+fn validate1() {
+   let r: syn::Result<XhtmlTag> = syn::parse_str(r#"<p><a href="x" HREF="y">b</a><br>c</br></p>"#);
+   let errors: Vec<String> = r.err().unwrap().into_iter().map(|e| e.to_string()).collect();
+   assert_eq!( errors, vec!["Attribute HREF of <a> is already given", "Void element <br> can not have children"] );
+}
+
+#[test]
+fn spread3() {
+   let t: XhtmlTag = parse_quote! { <a href="x" {{ if c {{ title="t" }} }} {{..attrs}}>b</a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("let attr_guard_1 : bool = c ;") );
+   assert!( ts.contains("if attr_guard_1 {") );
+   assert!( ts.contains("let mut attr_written : :: std :: vec :: Vec < & str > = :: std :: vec ! [\"href\"] ; if attr_guard_1 { attr_written . push (\"title\") ; }") );
+   assert!( ts.contains("spread_attributes (attrs , & attr_written)") );
+}
+
+#[test]
+fn spread2() {
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<Card {{..attrs}}></Card>");
+   assert!( r.is_err() );
+}
+
+#[test]
+fn attr5() {
+   let t: XhtmlTag = parse_quote! { <input disabled={{ flag }} value={{ v }}/> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("use :: rdxl_internals :: runtime :: { AttributeValue as _ , DisplayAttributeValue as _ } ;") );
+   assert!( ts.contains("(& (flag)) . push_attribute (\"disabled\" , false , & mut (stream))") );
+}
+
+#[test]
+fn merged1() {
+   let t: XhtmlTag = parse_quote! { <a class="btn " href="x" class:big class="wide"></a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<a class=\"btn big wide\" href=\"x\"></a>""#) );
+   let t: XhtmlTag = parse_quote! { <a style="margin:0;" style:color="red"></a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<a style=\"margin:0;color:red\"></a>""#) );
+}
+
+#[test]
+fn merged2() {
+   let t: XhtmlTag = parse_quote! { <a class="btn" class:active={{ on }} {{ if c {{ class="x" }} }} style:color={{ c }}></a> };
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains("let mut attr_list = :: rdxl_internals :: runtime :: AttributeList :: classes () ; attr_list . push (\"btn\") ; if on { attr_list . push (\"active\") ; } if c { attr_list . push (\"x\") ; }") );
+   assert!( ts.contains("attr_list . push_property (\"color\" , & (c)) ;") );
+   assert_eq!( ts.matches(" class=").count(), 1 );
+}
+
+#[test]
+fn merged3() {
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<a class:active=\"yes\"></a>");
+   assert_eq!( r.err().unwrap().to_string(), "class:active takes a {{ condition }} or no value" );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<a style:color></a>");
+   assert_eq!( r.err().unwrap().to_string(), "style takes a string or a {{ expr }}" );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<a xmlns:class=\"urn:c\" class:x=\"1\"></a>");
+   assert_eq!( r.err().unwrap().to_string(), "class:x is merged into class, but the prefix is also a namespace; write a namespaced attribute as \"class:x\"" );
+   let t: XhtmlTag = syn::parse_str("<a xmlns:class=\"urn:c\" \"class:x\"=\"1\" class=\"b\"></a>").unwrap();
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#"class:x=\"1\" class=\"b\""#) );
+}
+
+#[test]
+fn void1() {
+   let t: XhtmlTag = parse_quote! { <col/> };
+   assert!( t.to_token_stream().to_string().contains(r#""<col/>""#) );
+   let t: XhtmlTag = parse_quote! { <wbr></wbr> };
+   assert!( t.to_token_stream().to_string().contains(r#""<wbr/>""#) );
+   let t: XhtmlTag = parse_quote! { <iframe/> };
+   assert!( t.to_token_stream().to_string().contains(r#""<iframe></iframe>""#) );
+}
+
+#[test]
+fn escapable1() {
+   let t: XhtmlTag = syn::parse_str("<title>a &lt; b &amp; {{ c }}</title>").unwrap();
+   let ts = t.to_token_stream().to_string();
+   assert!( ts.contains(r#""<title>a &lt; b &amp; ""#) );
+   assert!( ts.contains("Render :: render (& (c) , & mut (stream))") );
+   assert_eq!( t.to_string(), "<title>a &lt; b &amp; {{ c }}</title>" );
+   let r: syn::Result<XhtmlTag> = syn::parse_str("<textarea>a <b>c</b> <i/></textarea>");
+   let errors: Vec<String> = r.err().unwrap().into_iter().map(|e| e.to_string()).collect();
+   assert_eq!( errors, vec!["Element <textarea> can only contain text, not <b>", "Element <textarea> can only contain text, not <i>"] );
+}
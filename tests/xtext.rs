@@ -0,0 +1,26 @@
+use rdxl_internals::xtext::Xtext;
+use syn::parse_quote;
+
+#[test]
+fn static1() {
+   let x: Xtext = parse_quote! { "Hello" "," " world" };
+   assert_eq!( x.as_static_str().unwrap(), "Hello, world" );
+}
+
+#[test]
+fn static2() {
+   let x: Xtext = parse_quote! { "Hello " [[ name ]] };
+   assert!( x.as_static_str().is_none() );
+}
+
+#[test]
+fn fragment1() {
+   let x: Xtext = syn::parse_str(r#"<> a b </>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some("ab".to_string()) );
+}
+
+#[test]
+fn output1() {
+   let x: Xtext = syn::parse_str(r#"<input disabled n=3/>"#).unwrap();
+   assert_eq!( x.as_static_str(), Some(r#"<input disabled n="3"/>"#.to_string()) );
+}
@@ -0,0 +1,34 @@
+use rdxl_internals::xtext::XtextExpr;
+use quote::ToTokens;
+use syn::parse_quote;
+
+#[test]
+fn match1() {
+   let e: XtextExpr = parse_quote! { {{ match n { 0 => {{ "none" }}, | 1 | 2 => {{ "few" }}, _ => {{ "many" }} } }} };
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.starts_with("match n {") );
+   assert!( ts.contains("| 1 | 2 => { :: rdxl_internals :: runtime :: Sink :: push_str (& mut (stream) , \"few\") ; }") );
+}
+
+#[test]
+fn if_let1() {
+   let e: XtextExpr = parse_quote! { {{ if let Some(x) = opt {{ {{ x }} }} else {{ "none" }} }} };
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.starts_with("if let Some (x) = opt {") );
+   assert!( ts.contains("else {") );
+}
+
+#[test]
+fn while_let1() {
+   let e: XtextExpr = parse_quote! { {{ while let Some(x) = stack.pop() {{ {{ x }} }} }} };
+   let ts = e.to_token_stream().to_string();
+   assert!( ts.starts_with("while let Some (x) = stack . pop () {") );
+}
+
+#[test]
+fn does_emit1() {
+   let e: XtextExpr = parse_quote! { {{ let x = 1; }} };
+   assert!( !e.does_emit() );
+   let e: XtextExpr = parse_quote! { {{ x }} };
+   assert!( e.does_emit() );
+}